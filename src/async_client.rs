@@ -0,0 +1,1038 @@
+use futures::{future, stream, Stream, StreamExt};
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::time::Instant;
+
+use crate::builder::default_async_client;
+use crate::cache::Caches;
+use crate::call::{self, Call, Config};
+use crate::hooks::Hooks;
+use crate::pages::Pager;
+use crate::pretty::{
+  self, format_definitions, format_definitions_markdown, Report,
+};
+use crate::ratelimit::{retry_after, RateLimits};
+use crate::report;
+use crate::requester::{check_size, etag};
+use crate::trace::RequestTrace;
+use crate::{
+  Audio, Definition, DefinitionsQuery, Etymology, Example, ExamplesQuery,
+  Fetched, Frequency, FrequencyBand, FrequencyQuery, FrequencyThresholds,
+  Operation, Paged, Phrase, PrettyOptions, Pronunciation, RateLimitStatus,
+  RelatedWords, RelatedWordsQuery, RelationshipType, RequestParts,
+  ResponseMeta, ScrabbleScore, Syllable, WordDetails, WordObject, WordReport,
+  WordnikBuilder, WordnikError, RHYME_LIMIT,
+};
+
+/// The asynchronous counterpart of [Wordnik](crate::Wordnik).
+///
+/// Requires the `async` feature. The methods mirror the blocking ones and
+/// return the same types.
+///
+/// # Example
+/// ```no_run
+/// use wordnik::AsyncWordnik;
+///
+/// #[tokio::main]
+/// async fn main() {
+///   let api = AsyncWordnik::new(
+///     "YOUR_API_KEY".to_string(),
+///     "https://api.wordnik.com/v4/word.json/".to_string(),
+///   );
+///
+///   let v = api.get_definitions("word").await.unwrap();
+///   println!("{:#?}", v);
+/// }
+/// ```
+///
+/// Like [Wordnik](crate::Wordnik), it is `Clone + Send + Sync`, and clones
+/// share the connection pool, the caches and the rate limits.
+///
+/// ```
+/// fn assert_send_sync<T: Clone + Send + Sync>() {}
+/// assert_send_sync::<wordnik::AsyncWordnik>();
+/// ```
+///
+/// Against a local server that is busy at first, and knows "apple" and
+/// "banana" but not their hyphenation:
+///
+/// ```
+/// # use std::io::{BufRead, BufReader, Write};
+/// # use std::net::TcpListener;
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// # use std::sync::Arc;
+/// use std::time::Duration;
+/// use wordnik::{AsyncWordnik, Operation, WordnikError};
+///
+/// # let server = TcpListener::bind("127.0.0.1:0").unwrap();
+/// # let base_url = format!("http://{}/v4/word.json/", server.local_addr().unwrap());
+/// let requests = Arc::new(AtomicUsize::new(0));
+/// # let counter = Arc::clone(&requests);
+/// # std::thread::spawn(move || {
+/// #   for stream in server.incoming() {
+/// #     let stream = stream.unwrap();
+/// #     let mut reader = BufReader::new(&stream);
+/// #     let mut request_line = String::new();
+/// #     reader.read_line(&mut request_line).unwrap();
+/// #     let mut line = String::new();
+/// #     while reader.read_line(&mut line).unwrap() > 2 {
+/// #       line.clear();
+/// #     }
+/// #     let n = counter.fetch_add(1, Ordering::SeqCst);
+/// #     let path = request_line.split(' ').nth(1).unwrap();
+/// #     let mut segments = path.split('?').next().unwrap().rsplit('/');
+/// #     let (operation, word) = (segments.next().unwrap(), segments.next().unwrap());
+/// #     let (status, body) = match operation {
+/// #       _ if n == 0 => ("429 Too Many Requests", String::new()),
+/// #       _ if word == "xyzzy" => ("404 Not Found", String::new()),
+/// #       "definitions" => ("200 OK", format!(
+/// #         r#"[{{"word":"{0}","text":"The word {0}.","attributionText":"","sourceDictionary":"","attributionUrl":"","wordnikUrl":""}}]"#,
+/// #         word,
+/// #       )),
+/// #       "examples" => ("200 OK", r#"{"examples":[{"text":"An apple a day."}]}"#.to_string()),
+/// #       "topExample" => ("200 OK", r#"{"text":"An apple a day."}"#.to_string()),
+/// #       "pronunciations" => ("200 OK", "[]".to_string()),
+/// #       _ => ("404 Not Found", String::new()),
+/// #     };
+/// #     let mut stream = &stream;
+/// #     write!(
+/// #       stream,
+/// #       "HTTP/1.1 {}\r\nconnection: close\r\ncontent-length: {}\r\n\r\n{}",
+/// #       status,
+/// #       body.len(),
+/// #       body,
+/// #     )
+/// #     .unwrap();
+/// #   }
+/// # });
+/// let api = AsyncWordnik::builder()
+///   .api_key("YOUR_API_KEY")
+///   .base_url(base_url)
+///   .retry(1, Duration::from_millis(10))
+///   .cache(Duration::from_secs(60), 100)
+///   .build_async()
+///   .unwrap();
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// // The first answer is a 429, and the request is retried.
+/// let defs = api.get_definitions("apple").await.unwrap();
+/// assert_eq!(defs[0].definition.as_deref(), Some("The word apple."));
+/// assert_eq!(requests.load(Ordering::SeqCst), 2);
+///
+/// // The definitions are cached now.
+/// api.get_definitions("apple").await.unwrap();
+/// assert_eq!(requests.load(Ordering::SeqCst), 2);
+///
+/// let details = api.get_word_details("banana").await.unwrap();
+/// assert_eq!(details.examples[0].text, "An apple a day.");
+/// assert!(details.pronunciations.is_empty());
+///
+/// let operations = [Operation::Definitions, Operation::TopExample, Operation::Hyphenation];
+/// let report = api.get_word_report_with("banana", &operations).await.unwrap();
+/// assert!(report.definitions.is_some());
+/// assert_eq!(report.top_example.unwrap().text, "An apple a day.");
+/// assert!(report.hyphenation.is_none());
+/// assert!(matches!(report.errors[..], [(Operation::Hyphenation, WordnikError::NotFound)]));
+///
+/// let words = ["apple", "xyzzy", "cherry", "damson"];
+/// let results = api.get_definitions_batch(&words, 2).await;
+/// let looked_up: Vec<&str> = results.iter().map(|(w, _)| w.as_str()).collect();
+/// assert_eq!(looked_up, words);
+/// assert!(matches!(results[1].1, Err(WordnikError::NotFound)));
+/// let damson = results[3].1.as_ref().unwrap();
+/// assert_eq!(damson[0].definition.as_deref(), Some("The word damson."));
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct AsyncWordnik {
+  config: Config,
+  client: reqwest::Client,
+  hooks: Hooks,
+  cache: Caches,
+  bypass_cache: bool,
+  rate_limits: RateLimits,
+}
+
+impl std::fmt::Debug for AsyncWordnik {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("AsyncWordnik")
+      .field("api_key", &crate::error::redact_key(&self.config.api_key))
+      .field("entry", &self.config.entry)
+      .finish_non_exhaustive()
+  }
+}
+
+impl AsyncWordnik {
+  /// Create a new API endpoint.
+  pub fn new(api_key: String, entry: String) -> AsyncWordnik {
+    AsyncWordnik {
+      config: Config::new_unchecked(api_key, entry),
+      client: default_async_client(),
+      hooks: Hooks::default(),
+      cache: Caches::default(),
+      bypass_cache: false,
+      rate_limits: RateLimits::default(),
+    }
+  }
+
+  /// Create a new API endpoint, validating the arguments.
+  ///
+  /// See [Wordnik::try_new](crate::Wordnik::try_new).
+  pub fn try_new(
+    api_key: String,
+    entry: String,
+  ) -> Result<AsyncWordnik, WordnikError> {
+    Ok(AsyncWordnik {
+      config: Config::new(api_key, entry)?,
+      client: default_async_client(),
+      hooks: Hooks::default(),
+      cache: Caches::default(),
+      bypass_cache: false,
+      rate_limits: RateLimits::default(),
+    })
+  }
+
+  /// Create a [WordnikBuilder]; finish it with
+  /// [build_async](WordnikBuilder::build_async).
+  pub fn builder() -> WordnikBuilder {
+    WordnikBuilder::new()
+  }
+
+  pub(crate) fn from_config(
+    config: Config,
+    client: reqwest::Client,
+    hooks: Hooks,
+    cache: Caches,
+    rate_limits: RateLimits,
+  ) -> AsyncWordnik {
+    AsyncWordnik {
+      config,
+      client,
+      hooks,
+      cache,
+      bypass_cache: false,
+      rate_limits,
+    }
+  }
+
+  /// Create a new API endpoint from the environment.
+  ///
+  /// See [Wordnik::from_env](crate::Wordnik::from_env).
+  pub fn from_env() -> Result<AsyncWordnik, WordnikError> {
+    WordnikBuilder::from_env()?.build_async()
+  }
+
+  /// Create a new API endpoint that sends every request through `client`.
+  ///
+  /// See [Wordnik::with_client](crate::Wordnik::with_client).
+  pub fn with_client(
+    api_key: String,
+    entry: String,
+    client: reqwest::Client,
+  ) -> AsyncWordnik {
+    AsyncWordnik {
+      config: Config::new_unchecked(api_key, entry),
+      client,
+      hooks: Hooks::default(),
+      cache: Caches::default(),
+      bypass_cache: false,
+      rate_limits: RateLimits::default(),
+    }
+  }
+
+  /// The entry URL requests are built from, always ending with a slash.
+  pub fn entry(&self) -> &str {
+    &self.config.entry
+  }
+
+  async fn make_request(
+    &self,
+    parts: &RequestParts,
+  ) -> Result<Fetched, WordnikError> {
+    let trace = RequestTrace::start(&parts.url);
+    let res = self
+      .client
+      .get(&parts.url)
+      .headers(parts.headers.clone())
+      .send()
+      .await?;
+    trace.status(res.status());
+    let rate_limit = RateLimitStatus::from_headers(res.headers());
+    match res.status() {
+      StatusCode::NOT_FOUND => return Err(WordnikError::NotFound),
+      StatusCode::NOT_MODIFIED => {
+        return Ok(Fetched::NotModified { rate_limit })
+      }
+      StatusCode::TOO_MANY_REQUESTS => {
+        let wait = retry_after(res.headers());
+        return Err(WordnikError::TooManyRequests(wait));
+      }
+      _ => {}
+    }
+    let mut res = res.error_for_status()?;
+    let etag = etag(res.headers());
+    let max = self.config.max_response_size;
+    check_size(res.content_length(), max)?;
+    let mut res_body = Vec::new();
+    while let Some(chunk) = res.chunk().await? {
+      let len = (res_body.len() + chunk.len()) as u64;
+      check_size(Some(len), max)?;
+      res_body.extend_from_slice(&chunk);
+    }
+    trace.body(res_body.len());
+
+    let body: Value = serde_json::from_slice(&res_body)?;
+    Ok(Fetched::Body {
+      body,
+      etag,
+      rate_limit,
+    })
+  }
+
+  async fn execute<T>(&self, call: Call<T>) -> Result<T, WordnikError> {
+    #[cfg(feature = "tracing")]
+    let span = call.span();
+    let res = async {
+      let mut parts = RequestParts {
+        url: self.config.url(&call)?,
+        headers: self.config.headers()?,
+      };
+      let key = self.config.cache_key(&call)?;
+      if !self.bypass_cache {
+        if let Some(res) = self.cache.before(&key, &mut parts.headers)? {
+          return call.parse(res);
+        }
+      }
+      if self.cache.offline {
+        return Err(WordnikError::Offline);
+      }
+      self.hooks.before(&mut parts);
+      let mut retries = 0;
+      let res = loop {
+        self.rate_limits.wait_async().await;
+        let start = Instant::now();
+        let res = self.make_request(&parts).await;
+        self.rate_limits.record(&res);
+        self
+          .hooks
+          .after(&ResponseMeta::new(&parts.url, start.elapsed(), &res));
+        match self.config.retry.delay(retries, &res) {
+          Some(delay) => futures_timer::Delay::new(delay).await,
+          None => break res,
+        }
+        retries += 1;
+      };
+      call.parse(self.cache.after(&key, res)?)
+    };
+    #[cfg(feature = "tracing")]
+    let res = tracing::Instrument::instrument(res, span);
+    res.await
+  }
+
+  /// The rate limits reported with the latest response.
+  ///
+  /// See [Wordnik::last_rate_limit](crate::Wordnik::last_rate_limit).
+  pub fn last_rate_limit(&self) -> Option<RateLimitStatus> {
+    self.rate_limits.last()
+  }
+
+  /// Forget all cached responses.
+  ///
+  /// See [Wordnik::clear_cache](crate::Wordnik::clear_cache).
+  pub fn clear_cache(&self) {
+    self.cache.clear();
+  }
+
+  /// Get a handle to the same endpoint that doesn't look in the cache.
+  ///
+  /// See [Wordnik::bypass_cache](crate::Wordnik::bypass_cache).
+  pub fn bypass_cache(&self) -> AsyncWordnik {
+    AsyncWordnik {
+      config: self.config.clone(),
+      client: self.client.clone(),
+      hooks: self.hooks.clone(),
+      cache: self.cache.clone(),
+      bypass_cache: true,
+      rate_limits: self.rate_limits.clone(),
+    }
+  }
+
+  /// Look up the word itself.
+  ///
+  /// See [Wordnik::get_word](crate::Wordnik::get_word).
+  pub async fn get_word(&self, word: &str) -> Result<WordObject, WordnikError> {
+    self.execute(call::word(word)).await
+  }
+
+  /// See
+  /// [Wordnik::spelling_suggestions](crate::Wordnik::spelling_suggestions).
+  pub async fn spelling_suggestions(
+    &self,
+    word: &str,
+  ) -> Result<Vec<String>, WordnikError> {
+    match self.get_word(word).await {
+      Ok(found) => Ok(call::suggestions(found, word)),
+      Err(WordnikError::NotFound) => Ok(Vec::new()),
+      Err(e) => Err(e),
+    }
+  }
+
+  /// Get definitions of a word.
+  ///
+  /// See [Wordnik::get_definitions](crate::Wordnik::get_definitions).
+  pub async fn get_definitions(
+    &self,
+    word: &str,
+  ) -> Result<Vec<Definition>, WordnikError> {
+    self
+      .get_definitions_with(word, &DefinitionsQuery::default())
+      .await
+  }
+
+  /// Get definitions of a word, as asked for by `query`.
+  ///
+  /// See [Wordnik::get_definitions_with](crate::Wordnik::get_definitions_with).
+  pub async fn get_definitions_with(
+    &self,
+    word: &str,
+    query: &DefinitionsQuery,
+  ) -> Result<Vec<Definition>, WordnikError> {
+    self.execute(call::definitions(word, query)).await
+  }
+
+  /// Get definitions of many words, with up to `concurrency` requests in
+  /// flight at once.
+  ///
+  /// See [Wordnik::get_definitions_batch](crate::Wordnik::get_definitions_batch).
+  pub async fn get_definitions_batch(
+    &self,
+    words: &[&str],
+    concurrency: usize,
+  ) -> Vec<(String, Result<Vec<Definition>, WordnikError>)> {
+    let mut results: Vec<_> = stream::iter(words.iter().enumerate())
+      .map(|(i, word)| async move {
+        (i, word.to_string(), self.get_definitions(word).await)
+      })
+      .buffer_unordered(concurrency.max(1))
+      .collect()
+      .await;
+    results.sort_by_key(|(i, _, _)| *i);
+    results
+      .into_iter()
+      .map(|(_, word, res)| (word, res))
+      .collect()
+  }
+
+  /// See [Wordnik::get_anki_notes](crate::Wordnik::get_anki_notes).
+  pub async fn get_anki_notes(
+    &self,
+    words: &[&str],
+    max_definitions: usize,
+    concurrency: usize,
+  ) -> Vec<(String, Result<String, WordnikError>)> {
+    let mut results: Vec<_> = stream::iter(words.iter().enumerate())
+      .map(|(i, word)| async move {
+        (
+          i,
+          word.to_string(),
+          self.anki_note(word, max_definitions).await,
+        )
+      })
+      .buffer_unordered(concurrency.max(1))
+      .collect()
+      .await;
+    results.sort_by_key(|(i, _, _)| *i);
+    results
+      .into_iter()
+      .map(|(_, word, res)| (word, res))
+      .collect()
+  }
+
+  async fn anki_note(
+    &self,
+    word: &str,
+    max_definitions: usize,
+  ) -> Result<String, WordnikError> {
+    let definitions = self.get_definitions(word).await?;
+    let query = ExamplesQuery::new().limit(1);
+    let examples = match self.get_examples_with(word, &query).await {
+      Err(WordnikError::NotFound) => Vec::new(),
+      res => res?,
+    };
+    Ok(crate::anki_note(
+      word,
+      &definitions,
+      examples.first(),
+      max_definitions,
+    ))
+  }
+
+  /// See [Wordnik::get_phrase_definitions](crate::Wordnik::get_phrase_definitions).
+  pub async fn get_phrase_definitions(
+    &self,
+    phrase: &str,
+  ) -> Result<Vec<Definition>, WordnikError> {
+    match self.get_definitions(&call::normalize_phrase(phrase)).await {
+      Err(WordnikError::NotFound) => Ok(Vec::new()),
+      res => res,
+    }
+  }
+
+  /// See [Wordnik::get_definitions_by_source](crate::Wordnik::get_definitions_by_source).
+  pub async fn get_definitions_by_source(
+    &self,
+    word: &str,
+  ) -> Result<Vec<(String, Vec<Definition>)>, WordnikError> {
+    Ok(crate::group_by_source(self.get_definitions(word).await?))
+  }
+
+  /// Get the first definition of a word, or `None` if it has none.
+  ///
+  /// See [Wordnik::get_definition](crate::Wordnik::get_definition).
+  pub async fn get_definition(
+    &self,
+    word: &str,
+  ) -> Result<Option<Definition>, WordnikError> {
+    Ok(self.get_definitions(word).await?.into_iter().next())
+  }
+
+  /// Get the most frequent part of speech among the definitions of a word.
+  ///
+  /// See [Wordnik::primary_part_of_speech](crate::Wordnik::primary_part_of_speech).
+  pub async fn primary_part_of_speech(
+    &self,
+    word: &str,
+  ) -> Result<Option<String>, WordnikError> {
+    Ok(call::primary_part_of_speech(
+      self.get_definitions(word).await?,
+    ))
+  }
+
+  /// Check whether the API knows a word.
+  ///
+  /// See [Wordnik::word_exists](crate::Wordnik::word_exists).
+  pub async fn word_exists(&self, word: &str) -> Result<bool, WordnikError> {
+    match self.execute(call::word_exists(word)).await {
+      Err(WordnikError::NotFound) => Ok(false),
+      res => res,
+    }
+  }
+
+  /// See [Wordnik::canonicalize](crate::Wordnik::canonicalize).
+  pub async fn canonicalize(&self, word: &str) -> Result<String, WordnikError> {
+    self.execute(call::canonical(word)).await
+  }
+
+  /// Get definitions of a word as a pretty string.
+  ///
+  /// See [Wordnik::get_definitions_pretty](crate::Wordnik::get_definitions_pretty).
+  pub async fn get_definitions_pretty(
+    &self,
+    word: &str,
+    textwidth: usize,
+    dicts: Vec<&str>,
+  ) -> Result<String, WordnikError> {
+    let opts = PrettyOptions::new().textwidth(textwidth).sources(&dicts);
+    self.get_definitions_pretty_with(word, &opts).await
+  }
+
+  /// Get definitions of a word as a pretty string, formatted as `opts`
+  /// says.
+  ///
+  /// See [Wordnik::get_definitions_pretty_with](crate::Wordnik::get_definitions_pretty_with).
+  pub async fn get_definitions_pretty_with(
+    &self,
+    word: &str,
+    opts: &PrettyOptions,
+  ) -> Result<String, WordnikError> {
+    let definitions = self.get_definitions(word).await?;
+    Ok(format_definitions(&definitions, opts))
+  }
+
+  /// Get definitions of a word as Markdown.
+  ///
+  /// See [Wordnik::get_definitions_markdown](crate::Wordnik::get_definitions_markdown).
+  pub async fn get_definitions_markdown(
+    &self,
+    word: &str,
+  ) -> Result<String, WordnikError> {
+    let definitions = self.get_definitions(word).await?;
+    Ok(format_definitions_markdown(word, &definitions))
+  }
+
+  /// Get pronunciations of a word.
+  ///
+  /// See [Wordnik::get_pronunciations](crate::Wordnik::get_pronunciations).
+  pub async fn get_pronunciations(
+    &self,
+    word: &str,
+  ) -> Result<Vec<Pronunciation>, WordnikError> {
+    self.execute(call::pronunciations(word)).await
+  }
+
+  /// Get IPA pronunciations of a word.
+  ///
+  /// See [Wordnik::get_ipa](crate::Wordnik::get_ipa).
+  pub async fn get_ipa(&self, word: &str) -> Result<Vec<String>, WordnikError> {
+    Ok(call::ipa_only(self.get_pronunciations(word).await?))
+  }
+
+  /// Get recorded pronunciations of a word.
+  ///
+  /// See [Wordnik::get_audio](crate::Wordnik::get_audio).
+  pub async fn get_audio(
+    &self,
+    word: &str,
+  ) -> Result<Vec<Audio>, WordnikError> {
+    self.execute(call::audio(word)).await
+  }
+
+  /// Get usage examples of a word.
+  ///
+  /// See [Wordnik::get_examples](crate::Wordnik::get_examples).
+  pub async fn get_examples(
+    &self,
+    word: &str,
+  ) -> Result<Vec<Example>, WordnikError> {
+    self
+      .get_examples_with(word, &ExamplesQuery::default())
+      .await
+  }
+
+  /// Get usage examples of a word, restricted by `query`.
+  ///
+  /// See [Wordnik::get_examples_with](crate::Wordnik::get_examples_with).
+  pub async fn get_examples_with(
+    &self,
+    word: &str,
+    query: &ExamplesQuery,
+  ) -> Result<Vec<Example>, WordnikError> {
+    Ok(self.get_examples_paged(word, query).await?.items)
+  }
+
+  /// Get usage examples of a word along with the total number available.
+  ///
+  /// See [Wordnik::get_examples_paged](crate::Wordnik::get_examples_paged).
+  pub async fn get_examples_paged(
+    &self,
+    word: &str,
+    query: &ExamplesQuery,
+  ) -> Result<Paged<Example>, WordnikError> {
+    self.execute(call::examples(word, query)).await
+  }
+
+  /// Get the example the API rates best.
+  ///
+  /// See [Wordnik::get_top_example](crate::Wordnik::get_top_example).
+  pub async fn get_top_example(
+    &self,
+    word: &str,
+  ) -> Result<Example, WordnikError> {
+    self.execute(call::top_example(word)).await
+  }
+
+  /// Get the text of the top example of a word, or `None` if it has none.
+  ///
+  /// See [Wordnik::first_example](crate::Wordnik::first_example).
+  pub async fn first_example(
+    &self,
+    word: &str,
+  ) -> Result<Option<String>, WordnikError> {
+    match self.get_top_example(word).await {
+      Ok(example) => Ok(call::example_text(example)),
+      Err(WordnikError::NotFound) => Ok(None),
+      Err(e) => Err(e),
+    }
+  }
+
+  /// Stream all usage examples of a word, fetching them a page at a time.
+  ///
+  /// See [Wordnik::examples_iter](crate::Wordnik::examples_iter).
+  pub fn examples_stream(
+    &self,
+    word: &str,
+  ) -> impl Stream<Item = Result<Example, WordnikError>> + '_ {
+    self.examples_stream_with(word, &ExamplesQuery::default())
+  }
+
+  /// Stream the usage examples of a word matching `query`, fetching them a
+  /// page at a time.
+  ///
+  /// See [Wordnik::examples_iter_with](crate::Wordnik::examples_iter_with).
+  pub fn examples_stream_with(
+    &self,
+    word: &str,
+    query: &ExamplesQuery,
+  ) -> impl Stream<Item = Result<Example, WordnikError>> + '_ {
+    let pager = Pager::new(query);
+    let word = word.to_string();
+    stream::unfold((pager, word), move |(mut pager, word)| async move {
+      let query = pager.next_query()?;
+      let res = self.get_examples_paged(&word, &query).await;
+      pager.advance(&res);
+      Some((res, (pager, word)))
+    })
+    .flat_map(|res| match res {
+      Ok(page) => stream::iter(page.items.into_iter().map(Ok)).left_stream(),
+      Err(e) => stream::once(future::ready(Err(e))).right_stream(),
+    })
+  }
+
+  /// Get words related to a word, grouped by relationship type.
+  ///
+  /// See [Wordnik::get_related_words](crate::Wordnik::get_related_words).
+  pub async fn get_related_words(
+    &self,
+    word: &str,
+  ) -> Result<Vec<RelatedWords>, WordnikError> {
+    self
+      .get_related_words_with(word, &RelatedWordsQuery::default())
+      .await
+  }
+
+  /// Get words related to a word, restricted by `query`.
+  ///
+  /// See [Wordnik::get_related_words_with](crate::Wordnik::get_related_words_with).
+  pub async fn get_related_words_with(
+    &self,
+    word: &str,
+    query: &RelatedWordsQuery,
+  ) -> Result<Vec<RelatedWords>, WordnikError> {
+    self.execute(call::related_words(word, query)).await
+  }
+
+  /// Get the synonyms and the antonyms of a word, in that order.
+  ///
+  /// See [Wordnik::get_synonyms_antonyms](crate::Wordnik::get_synonyms_antonyms).
+  pub async fn get_synonyms_antonyms(
+    &self,
+    word: &str,
+  ) -> Result<(Vec<String>, Vec<String>), WordnikError> {
+    match self.execute(call::synonyms_antonyms(word)).await {
+      Err(WordnikError::NotFound) => Ok((Vec::new(), Vec::new())),
+      res => res,
+    }
+  }
+
+  /// Get the synonyms of a word, without repeats.
+  ///
+  /// See [Wordnik::get_synonyms](crate::Wordnik::get_synonyms).
+  pub async fn get_synonyms(
+    &self,
+    word: &str,
+  ) -> Result<Vec<String>, WordnikError> {
+    self.related_of(word, RelationshipType::Synonym, None).await
+  }
+
+  /// Get the antonyms of a word, without repeats.
+  ///
+  /// See [Wordnik::get_antonyms](crate::Wordnik::get_antonyms).
+  pub async fn get_antonyms(
+    &self,
+    word: &str,
+  ) -> Result<Vec<String>, WordnikError> {
+    self.related_of(word, RelationshipType::Antonym, None).await
+  }
+
+  /// Get the hypernyms of a word, the more general terms it is a kind of.
+  ///
+  /// See [Wordnik::get_hypernyms](crate::Wordnik::get_hypernyms).
+  pub async fn get_hypernyms(
+    &self,
+    word: &str,
+  ) -> Result<Vec<String>, WordnikError> {
+    self
+      .related_of(word, RelationshipType::Hypernym, None)
+      .await
+  }
+
+  /// Get the hyponyms of a word, the more specific terms that are kinds
+  /// of it.
+  ///
+  /// See [Wordnik::get_hyponyms](crate::Wordnik::get_hyponyms).
+  pub async fn get_hyponyms(
+    &self,
+    word: &str,
+  ) -> Result<Vec<String>, WordnikError> {
+    self.related_of(word, RelationshipType::Hyponym, None).await
+  }
+
+  /// Get the single words that rhyme with a word.
+  ///
+  /// See [Wordnik::get_rhymes](crate::Wordnik::get_rhymes).
+  pub async fn get_rhymes(
+    &self,
+    word: &str,
+  ) -> Result<Vec<String>, WordnikError> {
+    let rhymes = self
+      .related_of(word, RelationshipType::Rhyme, Some(RHYME_LIMIT))
+      .await?;
+    Ok(call::single_words_except(rhymes, word))
+  }
+
+  async fn related_of(
+    &self,
+    word: &str,
+    relationship_type: RelationshipType,
+    limit: Option<u32>,
+  ) -> Result<Vec<String>, WordnikError> {
+    let call = call::related_of(word, relationship_type, limit);
+    match self.execute(call).await {
+      Err(WordnikError::NotFound) => Ok(Vec::new()),
+      res => res,
+    }
+  }
+
+  /// Get definitions, examples and pronunciations of a word in one go.
+  ///
+  /// The three requests are sent concurrently.
+  ///
+  /// See [Wordnik::get_word_details](crate::Wordnik::get_word_details).
+  pub async fn get_word_details(
+    &self,
+    word: &str,
+  ) -> Result<WordDetails, WordnikError> {
+    let (definitions, examples, pronunciations) = futures::try_join!(
+      self.get_definitions(word),
+      self.get_examples(word),
+      self.get_pronunciations(word),
+    )?;
+    Ok(WordDetails {
+      definitions,
+      examples,
+      pronunciations,
+    })
+  }
+
+  /// Get a report of a word from every endpoint with a section in
+  /// [WordReport], fetching the sections concurrently.
+  ///
+  /// See [Wordnik::get_word_report](crate::Wordnik::get_word_report).
+  pub async fn get_word_report(
+    &self,
+    word: &str,
+  ) -> Result<WordReport, WordnikError> {
+    self
+      .get_word_report_with(word, &report::REPORT_OPERATIONS)
+      .await
+  }
+
+  /// Get a report of a word with only the sections of `operations`.
+  ///
+  /// See [Wordnik::get_word_report_with](crate::Wordnik::get_word_report_with).
+  pub async fn get_word_report_with(
+    &self,
+    word: &str,
+    operations: &[Operation],
+  ) -> Result<WordReport, WordnikError> {
+    call::encode_segment(word)?;
+    let wants = |operation: Operation| operations.contains(&operation);
+    let (
+      definitions,
+      etymologies,
+      pronunciations,
+      hyphenation,
+      top_example,
+      related_words,
+      scrabble_score,
+      frequency,
+      audio,
+    ) = futures::join!(
+      fetch_if(wants(Operation::Definitions), self.get_definitions(word)),
+      fetch_if(wants(Operation::Etymologies), self.get_etymologies(word)),
+      fetch_if(
+        wants(Operation::Pronunciations),
+        self.get_pronunciations(word)
+      ),
+      fetch_if(wants(Operation::Hyphenation), self.get_hyphenation(word)),
+      fetch_if(wants(Operation::TopExample), self.get_top_example(word)),
+      fetch_if(wants(Operation::RelatedWords), self.get_related_words(word)),
+      fetch_if(
+        wants(Operation::ScrabbleScore),
+        self.get_scrabble_score(word)
+      ),
+      fetch_if(wants(Operation::Frequency), self.get_frequency(word)),
+      fetch_if(wants(Operation::Audio), self.get_audio(word)),
+    );
+    let mut report = WordReport::new(word);
+    report.definitions = report.section(Operation::Definitions, definitions);
+    report.etymologies = report.section(Operation::Etymologies, etymologies);
+    report.pronunciations =
+      report.section(Operation::Pronunciations, pronunciations);
+    report.hyphenation = report.section(Operation::Hyphenation, hyphenation);
+    report.top_example = report.section(Operation::TopExample, top_example);
+    report.related_words =
+      report.section(Operation::RelatedWords, related_words);
+    report.scrabble_score =
+      report.section(Operation::ScrabbleScore, scrabble_score);
+    report.frequency = report.section(Operation::Frequency, frequency);
+    report.audio = report.section(Operation::Audio, audio);
+    Ok(report)
+  }
+
+  /// Get the number of occurrences of a word, per year.
+  ///
+  /// See [Wordnik::get_frequency](crate::Wordnik::get_frequency).
+  pub async fn get_frequency(
+    &self,
+    word: &str,
+  ) -> Result<Frequency, WordnikError> {
+    self
+      .get_frequency_with(word, &FrequencyQuery::default())
+      .await
+  }
+
+  /// Get the number of occurrences of a word, restricted by `query`.
+  ///
+  /// See [Wordnik::get_frequency_with](crate::Wordnik::get_frequency_with).
+  pub async fn get_frequency_with(
+    &self,
+    word: &str,
+    query: &FrequencyQuery,
+  ) -> Result<Frequency, WordnikError> {
+    self.execute(call::frequency(word, query)).await
+  }
+
+  /// Classify a word as common, uncommon or rare by its total count.
+  ///
+  /// See [Wordnik::frequency_band](crate::Wordnik::frequency_band).
+  pub async fn frequency_band(
+    &self,
+    word: &str,
+  ) -> Result<FrequencyBand, WordnikError> {
+    self
+      .frequency_band_with(word, &FrequencyThresholds::default())
+      .await
+  }
+
+  /// Classify a word by its total count with `thresholds`.
+  ///
+  /// See [Wordnik::frequency_band_with](crate::Wordnik::frequency_band_with).
+  pub async fn frequency_band_with(
+    &self,
+    word: &str,
+    thresholds: &FrequencyThresholds,
+  ) -> Result<FrequencyBand, WordnikError> {
+    Ok(thresholds.band(self.get_frequency(word).await?.total_count))
+  }
+
+  /// Get the syllables of a word.
+  ///
+  /// See [Wordnik::get_hyphenation](crate::Wordnik::get_hyphenation).
+  pub async fn get_hyphenation(
+    &self,
+    word: &str,
+  ) -> Result<Vec<Syllable>, WordnikError> {
+    self.execute(call::hyphenation(word)).await
+  }
+
+  /// Get the two-word phrases a word appears in.
+  ///
+  /// See [Wordnik::get_phrases](crate::Wordnik::get_phrases).
+  pub async fn get_phrases(
+    &self,
+    word: &str,
+  ) -> Result<Vec<Phrase>, WordnikError> {
+    self.execute(call::phrases(word)).await
+  }
+
+  /// Get the etymologies of a word, parsed from their markup.
+  ///
+  /// See [Wordnik::get_etymologies](crate::Wordnik::get_etymologies).
+  pub async fn get_etymologies(
+    &self,
+    word: &str,
+  ) -> Result<Vec<Etymology>, WordnikError> {
+    self.execute(call::etymologies(word)).await
+  }
+
+  /// Get the Scrabble score of a word.
+  ///
+  /// See [Wordnik::get_scrabble_score](crate::Wordnik::get_scrabble_score).
+  pub async fn get_scrabble_score(
+    &self,
+    word: &str,
+  ) -> Result<ScrabbleScore, WordnikError> {
+    self.execute(call::scrabble_score(word)).await
+  }
+
+  /// Whether a word is playable in Scrabble.
+  ///
+  /// See [Wordnik::is_valid_scrabble_word](crate::Wordnik::is_valid_scrabble_word).
+  pub async fn is_valid_scrabble_word(
+    &self,
+    word: &str,
+  ) -> Result<bool, WordnikError> {
+    match self.get_scrabble_score(word).await {
+      Ok(score) => Ok(score.value > 0),
+      Err(WordnikError::NotFound) => Ok(false),
+      Err(e) => Err(e),
+    }
+  }
+
+  /// Get the response of any operation as JSON, as the API sent it.
+  ///
+  /// See [Wordnik::get_raw](crate::Wordnik::get_raw).
+  pub async fn get_raw(
+    &self,
+    word: &str,
+    operation: Operation,
+  ) -> Result<Value, WordnikError> {
+    self.execute(call::raw(word, operation)).await
+  }
+
+  /// Get a pretty report of a word.
+  ///
+  /// See [Wordnik::get_full_report_pretty](crate::Wordnik::get_full_report_pretty).
+  pub async fn get_full_report_pretty(
+    &self,
+    word: &str,
+  ) -> Result<String, WordnikError> {
+    self
+      .get_full_report_pretty_with(word, &PrettyOptions::default())
+      .await
+  }
+
+  /// Get a pretty report of a word, with the sections chosen by `opts`.
+  ///
+  /// See [Wordnik::get_full_report_pretty_with](crate::Wordnik::get_full_report_pretty_with).
+  pub async fn get_full_report_pretty_with(
+    &self,
+    word: &str,
+    opts: &PrettyOptions,
+  ) -> Result<String, WordnikError> {
+    let mut report = Report {
+      word,
+      hyphenation: Vec::new(),
+      ipa: Vec::new(),
+      definitions: Vec::new(),
+      examples: Vec::new(),
+    };
+    if opts.hyphenation {
+      report.hyphenation = call::or_empty(self.get_hyphenation(word).await)?;
+    }
+    if opts.pronunciation {
+      report.ipa = call::or_empty(self.get_ipa(word).await)?;
+    }
+    if opts.definitions {
+      report.definitions = call::or_empty(self.get_definitions(word).await)?;
+    }
+    if opts.examples != 0 {
+      let query = ExamplesQuery::new().limit(opts.examples);
+      report.examples =
+        call::or_empty(self.get_examples_with(word, &query).await)?;
+    }
+    Ok(pretty::format_report(&report, opts))
+  }
+}
+
+/// Await `future` if the section it fetches is `wanted`.
+async fn fetch_if<T>(
+  wanted: bool,
+  future: impl std::future::Future<Output = T>,
+) -> Option<T> {
+  match wanted {
+    true => Some(future.await),
+    false => None,
+  }
+}
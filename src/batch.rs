@@ -0,0 +1,47 @@
+//! Looking up many words at once with a bounded number of requests in
+//! flight.
+
+/// A polite number of requests to have in flight at once in batch
+/// lookups such as [Wordnik::get_definitions_batch](crate::Wordnik::get_definitions_batch).
+/// Much higher and the API is likely to rate-limit you.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Call `f` on every word from up to `concurrency` threads, returning the
+/// results in the order of `words`.
+#[cfg(feature = "blocking")]
+pub(crate) fn map_blocking<T, F>(
+  words: &[&str],
+  concurrency: usize,
+  f: F,
+) -> Vec<(String, T)>
+where
+  T: Send,
+  F: Fn(&str) -> T + Sync,
+{
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  use std::sync::Mutex;
+
+  let next = AtomicUsize::new(0);
+  let results: Mutex<Vec<Option<T>>> =
+    Mutex::new(words.iter().map(|_| None).collect());
+  std::thread::scope(|scope| {
+    for _ in 0..concurrency.clamp(1, words.len().max(1)) {
+      scope.spawn(|| loop {
+        let i = next.fetch_add(1, Ordering::Relaxed);
+        let Some(word) = words.get(i) else {
+          break;
+        };
+        let res = f(word);
+        results.lock().unwrap()[i] = Some(res);
+      });
+    }
+  });
+  let results = results.into_inner().unwrap();
+  words
+    .iter()
+    .zip(results)
+    .map(|(word, res)| {
+      (word.to_string(), res.expect("every word is looked up"))
+    })
+    .collect()
+}
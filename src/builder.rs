@@ -0,0 +1,892 @@
+#[cfg(feature = "async")]
+use crate::AsyncWordnik;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{HeaderName, HeaderValue, ACCEPT_LANGUAGE};
+
+use crate::cache::{Caches, DiskCache, MemoryCache};
+use crate::hooks::Hooks;
+use crate::ratelimit::{RateLimiter, RateLimits};
+#[cfg(feature = "blocking")]
+use crate::requester::SizeLimited;
+use crate::retry::Retry;
+use crate::{Config, RequestParts, ResponseMeta, WordnikError};
+#[cfg(feature = "blocking")]
+use crate::{Requester, Wordnik};
+
+/// The base URL of the official Wordnik word API.
+pub const DEFAULT_API_URL: &str = "https://api.wordnik.com/v4/word.json/";
+
+/// The `User-Agent` sent unless [WordnikBuilder::user_agent] says
+/// otherwise.
+pub const DEFAULT_USER_AGENT: &str =
+  concat!("wordnik-rs/", env!("CARGO_PKG_VERSION"));
+
+/// The longest response body read unless
+/// [WordnikBuilder::max_response_size] says otherwise: 8 MiB, far more
+/// than any normal response.
+pub const DEFAULT_MAX_RESPONSE_SIZE: u64 = 8 * 1024 * 1024;
+
+const API_KEY_VAR: &str = "WORDNIK_API_KEY";
+const API_URL_VAR: &str = "WORDNIK_API_URL";
+
+/// A builder for [Wordnik], created with [Wordnik::builder].
+///
+/// # Example
+/// ```
+/// use wordnik::Wordnik;
+///
+/// let api = Wordnik::builder().api_key("YOUR_API_KEY").build().unwrap();
+/// ```
+#[derive(Default)]
+pub struct WordnikBuilder {
+  api_key: Option<String>,
+  base_url: Option<String>,
+  #[cfg(feature = "blocking")]
+  requester: Option<Arc<dyn Requester>>,
+  user_agent: Option<String>,
+  headers: Vec<(String, String)>,
+  accept_language: Option<String>,
+  proxies: Vec<reqwest::Proxy>,
+  danger_accept_invalid_certs: bool,
+  hooks: Hooks,
+  cache: Option<(Duration, usize)>,
+  disk_cache: Option<(PathBuf, Duration)>,
+  offline: bool,
+  rate_limit: Option<(usize, Duration)>,
+  min_request_interval: Option<Duration>,
+  retry: Retry,
+  max_response_size: Option<u64>,
+}
+
+impl WordnikBuilder {
+  pub fn new() -> WordnikBuilder {
+    WordnikBuilder::default()
+  }
+
+  /// Set the API key. This is required.
+  ///
+  /// The key is always sent in the `api_key` header, marked as sensitive,
+  /// and never in the URL, so it stays out of server and proxy logs.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::builder()
+  ///   .api_key("SECRET_API_KEY")
+  ///   .requester(|url: &str, headers: &HeaderMap| {
+  ///     assert!(!url.contains("SECRET_API_KEY"));
+  ///     assert_eq!(headers["api_key"], "SECRET_API_KEY");
+  ///     assert!(headers["api_key"].is_sensitive());
+  ///     Ok(serde_json::json!([]))
+  ///   })
+  ///   .build()
+  ///   .unwrap();
+  /// api.get_definitions("word").unwrap();
+  /// ```
+  pub fn api_key(mut self, api_key: impl Into<String>) -> WordnikBuilder {
+    self.api_key = Some(api_key.into());
+    self
+  }
+
+  /// Use another base URL instead of [DEFAULT_API_URL], e.g. for a mirror
+  /// or a test server.
+  pub fn base_url(mut self, base_url: impl Into<String>) -> WordnikBuilder {
+    self.base_url = Some(base_url.into());
+    self
+  }
+
+  /// Send the requests of the blocking client through `requester`
+  /// instead of a default [reqwest::blocking::Client].
+  #[cfg(feature = "blocking")]
+  pub fn requester(
+    mut self,
+    requester: impl Requester + 'static,
+  ) -> WordnikBuilder {
+    self.requester = Some(Arc::new(requester));
+    self
+  }
+
+  /// Send `user_agent` instead of [DEFAULT_USER_AGENT] to identify your
+  /// application. To keep the crate's identifier, append it yourself.
+  ///
+  /// This configures the HTTP client built by the builder, so it has no
+  /// effect together with [requester](Self::requester).
+  ///
+  /// # Example
+  /// ```
+  /// use std::io::{BufRead, BufReader, Write};
+  /// use std::net::TcpListener;
+  /// use wordnik::{Wordnik, DEFAULT_USER_AGENT};
+  ///
+  /// let server = TcpListener::bind("127.0.0.1:0").unwrap();
+  /// let base_url = format!("http://{}/", server.local_addr().unwrap());
+  /// let handle = std::thread::spawn(move || {
+  ///   let (stream, _) = server.accept().unwrap();
+  ///   let mut reader = BufReader::new(&stream);
+  ///   let mut user_agent = String::new();
+  ///   let mut line = String::new();
+  ///   while reader.read_line(&mut line).unwrap() > 2 {
+  ///     if let Some(value) = line.to_lowercase().strip_prefix("user-agent:") {
+  ///       user_agent = value.trim().to_string();
+  ///     }
+  ///     line.clear();
+  ///   }
+  ///   let mut stream = &stream;
+  ///   write!(stream, "HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\n[]").unwrap();
+  ///   user_agent
+  /// });
+  ///
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .base_url(base_url)
+  ///   .user_agent(format!("my-dictionary-app/1.2 {}", DEFAULT_USER_AGENT))
+  ///   .build()
+  ///   .unwrap();
+  /// api.get_definitions("word").unwrap();
+  /// assert_eq!(
+  ///   handle.join().unwrap(),
+  ///   format!("my-dictionary-app/1.2 {}", DEFAULT_USER_AGENT),
+  /// );
+  /// ```
+  pub fn user_agent(mut self, user_agent: impl Into<String>) -> WordnikBuilder {
+    self.user_agent = Some(user_agent.into());
+    self
+  }
+
+  /// Send the header `name: value` with every request, e.g. for a gateway
+  /// in front of the API. Call it again to add more; a name added twice is
+  /// sent twice. The headers are sent alongside the API key and the
+  /// `User-Agent`, and can't replace the API key.
+  ///
+  /// An invalid name or value makes [build](Self::build) fail with
+  /// [WordnikError::InvalidHeader].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .header("X-Api-Gateway-Key", "GATEWAY_KEY")
+  ///   .requester(|_: &str, headers: &HeaderMap| {
+  ///     assert_eq!(headers["x-api-gateway-key"], "GATEWAY_KEY");
+  ///     assert_eq!(headers["api_key"], "YOUR_API_KEY");
+  ///     Ok(serde_json::json!([]))
+  ///   })
+  ///   .build()
+  ///   .unwrap();
+  /// api.get_definitions("word").unwrap();
+  ///
+  /// let err = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .header("Bad Name", "value")
+  ///   .build()
+  ///   .unwrap_err();
+  /// assert!(matches!(err, WordnikError::InvalidHeader(name) if name == "Bad Name"));
+  /// ```
+  pub fn header(
+    mut self,
+    name: impl Into<String>,
+    value: impl Into<String>,
+  ) -> WordnikBuilder {
+    self.headers.push((name.into(), value.into()));
+    self
+  }
+
+  /// Send `Accept-Language: languages` with every request, e.g. "fr" or
+  /// "fr-CA, fr;q=0.9", to get localized content where the API has it.
+  /// Calling it again replaces the value, and it overrides an
+  /// `Accept-Language` added with [header](Self::header).
+  ///
+  /// An invalid value makes [build](Self::build) fail with
+  /// [WordnikError::InvalidHeader].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .accept_language("fr")
+  ///   .requester(|_: &str, headers: &HeaderMap| {
+  ///     assert_eq!(headers["accept-language"], "fr");
+  ///     Ok(serde_json::json!([]))
+  ///   })
+  ///   .build()
+  ///   .unwrap();
+  /// api.get_definitions("word").unwrap();
+  /// ```
+  pub fn accept_language(
+    mut self,
+    languages: impl Into<String>,
+  ) -> WordnikBuilder {
+    self.accept_language = Some(languages.into());
+    self
+  }
+
+  /// Route requests through `proxy`. Call it again to add proxies for
+  /// other schemes.
+  ///
+  /// Without it, the proxies in the `HTTP_PROXY`, `HTTPS_PROXY` and
+  /// `NO_PROXY` environment variables are used. Like
+  /// [user_agent](Self::user_agent), it has no effect together with
+  /// [requester](Self::requester).
+  ///
+  /// # Example
+  /// ```
+  /// use std::io::{BufRead, BufReader, Write};
+  /// use std::net::TcpListener;
+  /// use wordnik::Wordnik;
+  ///
+  /// let proxy = TcpListener::bind("127.0.0.1:0").unwrap();
+  /// let proxy_url = format!("http://{}", proxy.local_addr().unwrap());
+  /// let handle = std::thread::spawn(move || {
+  ///   let (stream, _) = proxy.accept().unwrap();
+  ///   let mut reader = BufReader::new(&stream);
+  ///   let mut request_line = String::new();
+  ///   reader.read_line(&mut request_line).unwrap();
+  ///   let mut line = String::new();
+  ///   while reader.read_line(&mut line).unwrap() > 2 {
+  ///     line.clear();
+  ///   }
+  ///   let mut stream = &stream;
+  ///   write!(stream, "HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\n[]").unwrap();
+  ///   request_line
+  /// });
+  ///
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .base_url("http://api.wordnik.invalid/v4/word.json/")
+  ///   .proxy(reqwest::Proxy::http(proxy_url).unwrap())
+  ///   .build()
+  ///   .unwrap();
+  /// api.get_definitions("word").unwrap();
+  /// assert!(handle
+  ///   .join()
+  ///   .unwrap()
+  ///   .starts_with("GET http://api.wordnik.invalid/v4/word.json/word/definitions "));
+  /// ```
+  pub fn proxy(mut self, proxy: reqwest::Proxy) -> WordnikBuilder {
+    self.proxies.push(proxy);
+    self
+  }
+
+  /// Accept any TLS certificate, e.g. a self-signed one of a staging
+  /// server or mock. Off by default.
+  ///
+  /// # Warning
+  ///
+  /// This is dangerous: anyone between you and the server can then read
+  /// and change the traffic, including the API key. Only turn it on for
+  /// testing, and prefer trusting the proxy's CA in the system store.
+  ///
+  /// This configures the HTTP client built by the builder, so it has no
+  /// effect together with [requester](Self::requester).
+  ///
+  /// # Example
+  /// ```
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .base_url("https://staging.example.invalid/v4/word.json/")
+  ///   .danger_accept_invalid_certs(true)
+  ///   .build()
+  ///   .unwrap();
+  /// ```
+  pub fn danger_accept_invalid_certs(mut self, accept: bool) -> WordnikBuilder {
+    self.danger_accept_invalid_certs = accept;
+    self
+  }
+
+  /// Run `hook` before every request is sent, e.g. to add headers.
+  ///
+  /// Hooks run in the order they were added, whichever endpoint is called
+  /// and whatever the [requester](Self::requester).
+  ///
+  /// # Example
+  /// ```
+  /// use reqwest::header::{HeaderMap, HeaderValue};
+  /// use serde_json::json;
+  /// use std::sync::{Arc, Mutex};
+  /// use wordnik::Wordnik;
+  ///
+  /// let audit = Arc::new(Mutex::new(Vec::new()));
+  /// let sink = audit.clone();
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .requester(|_: &str, headers: &HeaderMap| {
+  ///     assert_eq!(headers["x-correlation-id"], "42");
+  ///     Ok(json!([]))
+  ///   })
+  ///   .on_request(|req| {
+  ///     let id = HeaderValue::from_static("42");
+  ///     req.headers_mut().insert("x-correlation-id", id);
+  ///   })
+  ///   .on_response(move |res| {
+  ///     sink.lock().unwrap().push((res.url().to_string(), res.status()));
+  ///   })
+  ///   .build()
+  ///   .unwrap();
+  /// api.get_hyphenation("word").unwrap();
+  ///
+  /// let audit = audit.lock().unwrap();
+  /// assert!(audit[0].0.ends_with("/word/hyphenation"));
+  /// assert_eq!(audit[0].1, Some(reqwest::StatusCode::OK));
+  /// ```
+  pub fn on_request(
+    mut self,
+    hook: impl Fn(&mut RequestParts) + Send + Sync + 'static,
+  ) -> WordnikBuilder {
+    self.hooks.on_request.push(Arc::new(hook));
+    self
+  }
+
+  /// Run `hook` after every response has been read or the request has
+  /// failed, e.g. to record statuses and latencies. See
+  /// [on_request](Self::on_request) for an example.
+  ///
+  /// Hooks only observe; errors are returned to the caller regardless.
+  pub fn on_response(
+    mut self,
+    hook: impl Fn(&ResponseMeta) + Send + Sync + 'static,
+  ) -> WordnikBuilder {
+    self.hooks.on_response.push(Arc::new(hook));
+    self
+  }
+
+  /// Keep up to `max_entries` responses in memory for `ttl`, so that
+  /// looking up the same word again doesn't send a request. When full,
+  /// the least recently used response is dropped.
+  ///
+  /// Responses are keyed by operation, word and parameters, and shared
+  /// by all threads using the client. Words are trimmed and lowercased in
+  /// the key, so "Run" and "run " share the response to "run". Errors are
+  /// never cached. See [Wordnik::clear_cache] and [Wordnik::bypass_cache].
+  ///
+  /// Expired responses that came with an `ETag` are revalidated with
+  /// `If-None-Match`, so a `304 Not Modified` keeps them for another `ttl`
+  /// without downloading them again.
+  ///
+  /// # Example
+  /// ```
+  /// use reqwest::header::HeaderMap;
+  /// use serde_json::json;
+  /// use std::sync::atomic::{AtomicUsize, Ordering};
+  /// use std::sync::Arc;
+  /// use std::time::Duration;
+  /// use wordnik::{Operation, Wordnik};
+  ///
+  /// let requests = Arc::new(AtomicUsize::new(0));
+  /// let counter = requests.clone();
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .cache(Duration::from_secs(600), 100)
+  ///   .requester(move |_: &str, _: &HeaderMap| {
+  ///     counter.fetch_add(1, Ordering::SeqCst);
+  ///     Ok(json!([{ "text": "word" }]))
+  ///   })
+  ///   .build()
+  ///   .unwrap();
+  ///
+  /// api.get_hyphenation("word").unwrap();
+  /// api.get_hyphenation("word").unwrap();
+  /// api.get_hyphenation(" Word ").unwrap();
+  /// assert_eq!(requests.load(Ordering::SeqCst), 1);
+  ///
+  /// // Other operations on the word are cached separately.
+  /// api.get_raw("word", Operation::Definitions).unwrap();
+  /// assert_eq!(requests.load(Ordering::SeqCst), 2);
+  ///
+  /// api.bypass_cache().get_hyphenation("word").unwrap();
+  /// assert_eq!(requests.load(Ordering::SeqCst), 3);
+  ///
+  /// api.clear_cache();
+  /// api.get_hyphenation("word").unwrap();
+  /// assert_eq!(requests.load(Ordering::SeqCst), 4);
+  /// ```
+  pub fn cache(mut self, ttl: Duration, max_entries: usize) -> WordnikBuilder {
+    self.cache = Some((ttl, max_entries));
+    self
+  }
+
+  /// Store responses as JSON files in `dir` for `ttl`, so they survive
+  /// restarts. The files are named after the word, operation and
+  /// parameters, and never contain the API key.
+  ///
+  /// Expired or unreadable files are fetched again, and failing to write
+  /// a file doesn't fail the call. With [cache](Self::cache) too, the
+  /// memory is looked in first.
+  ///
+  /// # Example
+  /// ```no_run
+  /// use std::time::Duration;
+  /// use wordnik::Wordnik;
+  ///
+  /// let day = Duration::from_secs(24 * 60 * 60);
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .disk_cache("/home/me/.cache/wordnik", 30 * day)
+  ///   .build()
+  ///   .unwrap();
+  /// ```
+  pub fn disk_cache(
+    mut self,
+    dir: impl Into<PathBuf>,
+    ttl: Duration,
+  ) -> WordnikBuilder {
+    self.disk_cache = Some((dir.into(), ttl));
+    self
+  }
+
+  /// Only answer from the caches, failing with [WordnikError::Offline]
+  /// instead of sending a request. A handle from
+  /// [bypass_cache](Wordnik::bypass_cache) always fails.
+  ///
+  /// # Example
+  /// ```
+  /// use reqwest::header::HeaderMap;
+  /// use std::time::Duration;
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let dir = std::env::temp_dir().join("wordnik-offline-doctest");
+  /// let api = Wordnik::builder()
+  ///   .api_key("SECRET")
+  ///   .disk_cache(&dir, Duration::from_secs(600))
+  ///   .requester(|_: &str, _: &HeaderMap| Ok(serde_json::json!([])))
+  ///   .build()
+  ///   .unwrap();
+  /// api.clear_cache();
+  /// api.get_hyphenation("word").unwrap();
+  ///
+  /// let api = Wordnik::builder()
+  ///   .api_key("SECRET")
+  ///   .disk_cache(&dir, Duration::from_secs(600))
+  ///   .offline(true)
+  ///   .requester(|_: &str, _: &HeaderMap| panic!("went online"))
+  ///   .build()
+  ///   .unwrap();
+  /// assert!(api.get_hyphenation("word").unwrap().is_empty());
+  /// assert!(matches!(api.get_hyphenation("other"), Err(WordnikError::Offline)));
+  ///
+  /// // Bypassing the cache doesn't go online either.
+  /// let res = api.bypass_cache().get_hyphenation("word");
+  /// assert!(matches!(res, Err(WordnikError::Offline)));
+  ///
+  /// for file in std::fs::read_dir(&dir).unwrap() {
+  ///   let json = std::fs::read_to_string(file.unwrap().path()).unwrap();
+  ///   assert!(!json.contains("SECRET"));
+  /// }
+  /// ```
+  pub fn offline(mut self, offline: bool) -> WordnikBuilder {
+    self.offline = offline;
+    self
+  }
+
+  /// Send at most `max_requests` requests in any `window`, e.g. 100 per
+  /// minute, delaying requests that would go over. Blocking clients sleep
+  /// and async ones wait without blocking the thread.
+  ///
+  /// The budget is shared by all endpoints and by clones of the client.
+  /// Responses from the cache don't count against it.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use std::time::{Duration, Instant};
+  /// # use reqwest::header::HeaderMap;
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .rate_limit(2, Duration::from_millis(200))
+  ///   .requester(|_: &str, _: &HeaderMap| Ok(serde_json::json!([])))
+  ///   .build()
+  ///   .unwrap();
+  /// let clone = api.clone();
+  ///
+  /// let start = Instant::now();
+  /// api.get_hyphenation("one").unwrap();
+  /// clone.get_definitions("two").unwrap();
+  /// assert!(start.elapsed() < Duration::from_millis(200));
+  /// api.get_pronunciations("three").unwrap();
+  /// assert!(start.elapsed() >= Duration::from_millis(200));
+  /// ```
+  pub fn rate_limit(
+    mut self,
+    max_requests: usize,
+    window: Duration,
+  ) -> WordnikBuilder {
+    self.rate_limit = Some((max_requests, window));
+    self
+  }
+
+  /// Leave at least `interval` between the requests sent, e.g. 600ms to
+  /// stay under 100 per minute, delaying requests sent sooner. Like
+  /// [rate_limit](Self::rate_limit), which it can be combined with, it is
+  /// shared by clones of the client, applies to every
+  /// [retry](Self::retry) too, and doesn't count responses from the
+  /// cache.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use std::time::{Duration, Instant};
+  /// # use reqwest::header::HeaderMap;
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .min_request_interval(Duration::from_millis(50))
+  ///   .requester(|_: &str, _: &HeaderMap| Ok(serde_json::json!([])))
+  ///   .build()
+  ///   .unwrap();
+  ///
+  /// let start = Instant::now();
+  /// for word in ["one", "two", "three"] {
+  ///   api.get_hyphenation(word).unwrap();
+  /// }
+  /// assert!(start.elapsed() >= Duration::from_millis(100));
+  /// ```
+  pub fn min_request_interval(mut self, interval: Duration) -> WordnikBuilder {
+    self.min_request_interval = Some(interval);
+    self
+  }
+
+  /// Retry a request up to `max_retries` times when it times out, can't
+  /// connect, or gets a 429 or 5xx response, waiting `backoff` before the
+  /// first retry and twice as long before each one after it. A 429 whose
+  /// `Retry-After` header asks for a longer wait, in seconds or as a date,
+  /// waits that long instead. Other errors, such as
+  /// [NotFound](WordnikError::NotFound), are returned right away.
+  ///
+  /// Every attempt waits for the [rate limit](Self::rate_limit) and is
+  /// seen by [on_response](Self::on_response) hooks.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use std::sync::atomic::{AtomicUsize, Ordering};
+  /// # use std::sync::Arc;
+  /// # use std::time::Duration;
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let attempts = Arc::new(AtomicUsize::new(0));
+  /// let counter = Arc::clone(&attempts);
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .base_url("http://127.0.0.1:9/v4/word.json/")
+  ///   .retry(2, Duration::from_millis(10))
+  ///   .on_response(move |_| {
+  ///     counter.fetch_add(1, Ordering::Relaxed);
+  ///   })
+  ///   .build()
+  ///   .unwrap();
+  /// let err = api.get_definitions("word").unwrap_err();
+  /// assert!(matches!(err, WordnikError::Http(e) if e.is_connect()));
+  /// assert_eq!(attempts.load(Ordering::Relaxed), 3);
+  /// ```
+  ///
+  /// A requester answering a 429 with `Retry-After: 1`:
+  ///
+  /// ```
+  /// # use std::sync::atomic::{AtomicUsize, Ordering};
+  /// # use std::time::{Duration, Instant};
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let attempts = AtomicUsize::new(0);
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .retry(1, Duration::from_millis(10))
+  ///   .requester(move |_: &str, _: &HeaderMap| {
+  ///     match attempts.fetch_add(1, Ordering::SeqCst) {
+  ///       0 => Err(WordnikError::TooManyRequests(Some(Duration::from_secs(1)))),
+  ///       _ => Ok(json!([])),
+  ///     }
+  ///   })
+  ///   .build()
+  ///   .unwrap();
+  /// let start = Instant::now();
+  /// api.get_definitions("word").unwrap();
+  /// assert!(start.elapsed() >= Duration::from_secs(1));
+  /// ```
+  ///
+  /// The header is read from the server's response:
+  ///
+  /// ```
+  /// use std::io::{BufRead, BufReader, Write};
+  /// use std::net::TcpListener;
+  /// use std::time::{Duration, Instant};
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let server = TcpListener::bind("127.0.0.1:0").unwrap();
+  /// let base_url = format!("http://{}/", server.local_addr().unwrap());
+  /// std::thread::spawn(move || {
+  ///   for stream in server.incoming() {
+  ///     let stream = stream.unwrap();
+  ///     let mut reader = BufReader::new(&stream);
+  ///     let mut line = String::new();
+  ///     while reader.read_line(&mut line).unwrap() > 2 {
+  ///       line.clear();
+  ///     }
+  ///     let mut stream = &stream;
+  ///     write!(
+  ///       stream,
+  ///       "HTTP/1.1 429 Too Many Requests\r\nretry-after: 1\r\n\
+  ///        connection: close\r\ncontent-length: 0\r\n\r\n",
+  ///     )
+  ///     .unwrap();
+  ///   }
+  /// });
+  ///
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .base_url(base_url)
+  ///   .retry(1, Duration::from_millis(10))
+  ///   .build()
+  ///   .unwrap();
+  /// let start = Instant::now();
+  /// let err = api.get_definitions("word").unwrap_err();
+  /// assert!(start.elapsed() >= Duration::from_secs(1));
+  /// assert!(matches!(
+  ///   err,
+  ///   WordnikError::TooManyRequests(Some(wait)) if wait == Duration::from_secs(1)
+  /// ));
+  /// ```
+  pub fn retry(
+    mut self,
+    max_retries: usize,
+    backoff: Duration,
+  ) -> WordnikBuilder {
+    self.retry = Retry {
+      max_retries,
+      backoff,
+    };
+    self
+  }
+
+  /// Fail with [ResponseTooLarge](WordnikError::ResponseTooLarge) rather
+  /// than read a response body longer than `bytes`, by default
+  /// [DEFAULT_MAX_RESPONSE_SIZE]. A `Content-Length` over the limit fails
+  /// before the body is read, and a body without one stops being read
+  /// at the limit.
+  ///
+  /// This applies to the HTTP client built by the builder, so it has no
+  /// effect together with [requester](Self::requester).
+  ///
+  /// # Example
+  /// ```
+  /// use std::io::{BufRead, BufReader, Write};
+  /// use std::net::TcpListener;
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let server = TcpListener::bind("127.0.0.1:0").unwrap();
+  /// let base_url = format!("http://{}/", server.local_addr().unwrap());
+  /// let body = format!("[{}]", vec!["{}"; 100].join(","));
+  /// let handle = std::thread::spawn(move || {
+  ///   // With a Content-Length, then without one.
+  ///   for headers in [format!("content-length: {}\r\n", body.len()), String::new()] {
+  ///     let (stream, _) = server.accept().unwrap();
+  ///     let mut reader = BufReader::new(&stream);
+  ///     let mut line = String::new();
+  ///     while reader.read_line(&mut line).unwrap() > 2 {
+  ///       line.clear();
+  ///     }
+  ///     let mut stream = &stream;
+  ///     write!(stream, "HTTP/1.1 200 OK\r\nconnection: close\r\n{}\r\n{}", headers, body)
+  ///       .unwrap();
+  ///   }
+  /// });
+  ///
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .base_url(base_url)
+  ///   .max_response_size(64)
+  ///   .build()
+  ///   .unwrap();
+  /// for _ in 0..2 {
+  ///   let err = api.get_hyphenation("word").unwrap_err();
+  ///   assert!(matches!(err, WordnikError::ResponseTooLarge(64)));
+  /// }
+  /// handle.join().unwrap();
+  /// ```
+  pub fn max_response_size(mut self, bytes: u64) -> WordnikBuilder {
+    self.max_response_size = Some(bytes);
+    self
+  }
+
+  /// Take the API key from `WORDNIK_API_KEY` and, if set, the base URL from
+  /// `WORDNIK_API_URL`. Surrounding whitespace is trimmed from both.
+  pub(crate) fn from_env() -> Result<WordnikBuilder, WordnikError> {
+    let api_key = std::env::var(API_KEY_VAR)
+      .ok()
+      .map(|key| key.trim().to_string())
+      .filter(|key| !key.is_empty())
+      .ok_or(WordnikError::MissingEnvVar(API_KEY_VAR))?;
+    let mut builder = WordnikBuilder::new().api_key(api_key);
+    if let Ok(url) = std::env::var(API_URL_VAR) {
+      builder = builder.base_url(url.trim());
+    }
+    Ok(builder)
+  }
+
+  /// Build the blocking client.
+  ///
+  /// Fails if the API key is missing or the base URL doesn't parse.
+  ///
+  /// # Example
+  /// ```
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let res = Wordnik::builder().base_url("not a url").api_key("KEY").build();
+  /// assert!(matches!(res, Err(WordnikError::InvalidEntry(_))));
+  ///
+  /// let res = Wordnik::builder().build();
+  /// assert!(matches!(res, Err(WordnikError::EmptyApiKey)));
+  /// ```
+  #[cfg(feature = "blocking")]
+  pub fn build(self) -> Result<Wordnik, WordnikError> {
+    let config = self.config()?;
+    let requester: Arc<dyn Requester> = match &self.requester {
+      Some(requester) => requester.clone(),
+      None => Arc::new(SizeLimited {
+        client: self.blocking_client()?,
+        max_response_size: config.max_response_size,
+      }),
+    };
+    Ok(Wordnik {
+      config,
+      requester,
+      hooks: self.hooks.clone(),
+      cache: self.new_cache(),
+      bypass_cache: false,
+      rate_limits: self.new_rate_limits(),
+    })
+  }
+
+  /// Build the async client.
+  #[cfg(feature = "async")]
+  pub fn build_async(self) -> Result<AsyncWordnik, WordnikError> {
+    Ok(AsyncWordnik::from_config(
+      self.config()?,
+      self.async_client()?,
+      self.hooks.clone(),
+      self.new_cache(),
+      self.new_rate_limits(),
+    ))
+  }
+
+  fn config(&self) -> Result<Config, WordnikError> {
+    let mut config = Config::new(
+      self.api_key.clone().unwrap_or_default(),
+      self
+        .base_url
+        .clone()
+        .unwrap_or_else(|| DEFAULT_API_URL.to_string()),
+    )?;
+    for (name, value) in &self.headers {
+      let invalid = || WordnikError::InvalidHeader(name.clone());
+      let name =
+        HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid())?;
+      let value = HeaderValue::from_str(value).map_err(|_| invalid())?;
+      config.extra_headers.append(name, value);
+    }
+    config.retry = self.retry;
+    if let Some(max) = self.max_response_size {
+      config.max_response_size = max;
+    }
+    if let Some(languages) = &self.accept_language {
+      let value = HeaderValue::from_str(languages).map_err(|_| {
+        WordnikError::InvalidHeader(ACCEPT_LANGUAGE.to_string())
+      })?;
+      config.extra_headers.insert(ACCEPT_LANGUAGE, value);
+    }
+    Ok(config)
+  }
+
+  fn new_cache(&self) -> Caches {
+    Caches {
+      memory: self
+        .cache
+        .map(|(ttl, max_entries)| Arc::new(MemoryCache::new(ttl, max_entries))),
+      disk: self
+        .disk_cache
+        .clone()
+        .map(|(dir, ttl)| Arc::new(DiskCache::new(dir, ttl))),
+      offline: self.offline,
+    }
+  }
+
+  fn new_rate_limits(&self) -> RateLimits {
+    let mut limiters = Vec::new();
+    if let Some((max_requests, window)) = self.rate_limit {
+      limiters.push(RateLimiter::new(max_requests, window));
+    }
+    if let Some(interval) = self.min_request_interval {
+      limiters.push(RateLimiter::new(1, interval));
+    }
+    RateLimits::new(limiters)
+  }
+
+  fn user_agent_or_default(&self) -> &str {
+    self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT)
+  }
+
+  #[cfg(feature = "blocking")]
+  fn blocking_client(&self) -> Result<reqwest::blocking::Client, WordnikError> {
+    let mut builder = reqwest::blocking::Client::builder()
+      .user_agent(self.user_agent_or_default())
+      .danger_accept_invalid_certs(self.danger_accept_invalid_certs);
+    for proxy in &self.proxies {
+      builder = builder.proxy(proxy.clone());
+    }
+    #[cfg(feature = "native-tls")]
+    let builder = builder.use_native_tls();
+    #[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+    let builder = builder.use_rustls_tls();
+    Ok(builder.build()?)
+  }
+
+  #[cfg(feature = "async")]
+  fn async_client(&self) -> Result<reqwest::Client, WordnikError> {
+    let mut builder = reqwest::Client::builder()
+      .user_agent(self.user_agent_or_default())
+      .danger_accept_invalid_certs(self.danger_accept_invalid_certs);
+    for proxy in &self.proxies {
+      builder = builder.proxy(proxy.clone());
+    }
+    #[cfg(feature = "native-tls")]
+    let builder = builder.use_native_tls();
+    #[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+    let builder = builder.use_rustls_tls();
+    Ok(builder.build()?)
+  }
+}
+
+/// The client used when none is given, which panics like
+/// [reqwest::blocking::Client::new] if it can't be built.
+#[cfg(feature = "blocking")]
+pub(crate) fn default_blocking_client() -> reqwest::blocking::Client {
+  WordnikBuilder::new()
+    .blocking_client()
+    .expect("failed to build the HTTP client")
+}
+
+#[cfg(feature = "async")]
+pub(crate) fn default_async_client() -> reqwest::Client {
+  WordnikBuilder::new()
+    .async_client()
+    .expect("failed to build the HTTP client")
+}
@@ -0,0 +1,312 @@
+//! Caches of responses, turned on with
+//! [WordnikBuilder::cache](crate::WordnikBuilder::cache) and
+//! [WordnikBuilder::disk_cache](crate::WordnikBuilder::disk_cache).
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{HeaderMap, HeaderValue, IF_NONE_MATCH};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::redact_url;
+use crate::{Fetched, WordnikError};
+
+/// The caches a client looks in before sending a request.
+#[derive(Clone, Default)]
+pub(crate) struct Caches {
+  pub(crate) memory: Option<Arc<MemoryCache>>,
+  pub(crate) disk: Option<Arc<DiskCache>>,
+  /// Fail instead of sending requests on a cache miss.
+  pub(crate) offline: bool,
+}
+
+impl Caches {
+  /// Look for a response to `url` before it is sent. A fresh one is
+  /// returned; for a stale one with an ETag, `If-None-Match` is added to
+  /// `headers` so that the server can confirm it with 304 Not Modified.
+  pub(crate) fn before(
+    &self,
+    url: &str,
+    headers: &mut HeaderMap,
+  ) -> Result<Option<Value>, WordnikError> {
+    let cached = self.get(url);
+    if let Some(Cached {
+      value, fresh: true, ..
+    }) = cached
+    {
+      return Ok(Some(value));
+    }
+    let etag = cached.and_then(|cached| cached.etag);
+    if let Some(etag) = etag.and_then(|etag| HeaderValue::from_str(&etag).ok())
+    {
+      headers.insert(IF_NONE_MATCH, etag);
+    }
+    Ok(None)
+  }
+
+  /// Store the response to `url`, or on 304 Not Modified, keep the cached
+  /// one for another TTL.
+  pub(crate) fn after(
+    &self,
+    url: &str,
+    res: Result<Fetched, WordnikError>,
+  ) -> Result<Value, WordnikError> {
+    let (value, etag) = match res? {
+      Fetched::Body { body, etag, .. } => (body, etag),
+      Fetched::NotModified { .. } => {
+        let cached = self.get(url).ok_or_else(|| {
+          WordnikError::Json(serde::de::Error::custom(
+            "304 Not Modified without a cached response",
+          ))
+        })?;
+        (cached.value, cached.etag)
+      }
+    };
+    if let Some(memory) = &self.memory {
+      memory.insert(url, &value, etag.as_deref());
+    }
+    if let Some(disk) = &self.disk {
+      disk.insert(url, &value, etag.as_deref());
+    }
+    Ok(value)
+  }
+
+  /// The freshest cached response, preferring memory.
+  fn get(&self, url: &str) -> Option<Cached> {
+    let memory = self.memory.as_ref().and_then(|m| m.get(url));
+    if let Some(cached @ Cached { fresh: true, .. }) = memory {
+      return Some(cached);
+    }
+    match self.disk.as_ref().and_then(|d| d.get(url)) {
+      Some(cached) if cached.fresh => {
+        if let Some(memory) = &self.memory {
+          memory.insert(url, &cached.value, cached.etag.as_deref());
+        }
+        Some(cached)
+      }
+      disk => memory.or(disk),
+    }
+  }
+
+  pub(crate) fn clear(&self) {
+    if let Some(memory) = &self.memory {
+      memory.clear();
+    }
+    if let Some(disk) = &self.disk {
+      disk.clear();
+    }
+  }
+}
+
+/// A cached response. Expired ones are only kept if they have an ETag to
+/// revalidate them with.
+struct Cached {
+  value: Value,
+  etag: Option<String>,
+  fresh: bool,
+}
+
+struct Entry {
+  value: Value,
+  etag: Option<String>,
+  expires: Instant,
+  last_used: u64,
+}
+
+#[derive(Default)]
+struct Entries {
+  map: HashMap<String, Entry>,
+  clock: u64,
+}
+
+/// Responses keyed by their URL, which holds the operation, the word and
+/// the parameters but not the API key. When full, the least recently used
+/// response is evicted.
+pub(crate) struct MemoryCache {
+  ttl: Duration,
+  max_entries: usize,
+  entries: Mutex<Entries>,
+}
+
+impl MemoryCache {
+  pub(crate) fn new(ttl: Duration, max_entries: usize) -> MemoryCache {
+    MemoryCache {
+      ttl,
+      max_entries,
+      entries: Mutex::default(),
+    }
+  }
+
+  fn get(&self, url: &str) -> Option<Cached> {
+    let mut entries = self.entries.lock().unwrap();
+    entries.clock += 1;
+    let clock = entries.clock;
+    let entry = entries.map.get_mut(url)?;
+    let fresh = entry.expires > Instant::now();
+    if !fresh && entry.etag.is_none() {
+      entries.map.remove(url);
+      return None;
+    }
+    entry.last_used = clock;
+    Some(Cached {
+      value: entry.value.clone(),
+      etag: entry.etag.clone(),
+      fresh,
+    })
+  }
+
+  fn insert(&self, url: &str, value: &Value, etag: Option<&str>) {
+    if self.max_entries == 0 {
+      return;
+    }
+    let mut entries = self.entries.lock().unwrap();
+    entries.clock += 1;
+    let now = Instant::now();
+    let is_new = !entries.map.contains_key(url);
+    if is_new && entries.map.len() >= self.max_entries {
+      entries.map.retain(|_, entry| entry.expires > now);
+    }
+    if is_new && entries.map.len() >= self.max_entries {
+      let oldest = entries
+        .map
+        .iter()
+        .min_by_key(|(_, entry)| entry.last_used)
+        .map(|(url, _)| url.clone());
+      if let Some(oldest) = oldest {
+        entries.map.remove(&oldest);
+      }
+    }
+    let entry = Entry {
+      value: value.clone(),
+      etag: etag.map(str::to_string),
+      expires: now + self.ttl,
+      last_used: entries.clock,
+    };
+    entries.map.insert(url.to_string(), entry);
+  }
+
+  fn clear(&self) {
+    self.entries.lock().unwrap().map.clear();
+  }
+}
+
+/// One cached response on disk. The URL is only kept for humans reading
+/// the file, with the API key redacted.
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+  url: String,
+  /// Seconds since the Unix epoch.
+  stored: u64,
+  #[serde(default)]
+  etag: Option<String>,
+  body: Value,
+}
+
+/// Responses stored as JSON files in a directory, one per request. Files
+/// that are expired or can't be read count as misses, and failures to
+/// write are ignored, since the response can always be fetched again.
+pub(crate) struct DiskCache {
+  dir: PathBuf,
+  ttl: Duration,
+}
+
+impl DiskCache {
+  pub(crate) fn new(dir: PathBuf, ttl: Duration) -> DiskCache {
+    DiskCache { dir, ttl }
+  }
+
+  fn get(&self, url: &str) -> Option<Cached> {
+    let url = redacted(url);
+    let json = fs::read_to_string(self.dir.join(file_name(&url))).ok()?;
+    let entry: DiskEntry = serde_json::from_str(&json).ok()?;
+    if entry.url != url {
+      return None;
+    }
+    let age = now().checked_sub(entry.stored)?;
+    let fresh = Duration::from_secs(age) < self.ttl;
+    if !fresh && entry.etag.is_none() {
+      return None;
+    }
+    Some(Cached {
+      value: entry.body,
+      etag: entry.etag,
+      fresh,
+    })
+  }
+
+  fn insert(&self, url: &str, value: &Value, etag: Option<&str>) {
+    let url = redacted(url);
+    let path = self.dir.join(file_name(&url));
+    let entry = DiskEntry {
+      url,
+      stored: now(),
+      etag: etag.map(str::to_string),
+      body: value.clone(),
+    };
+    if let Ok(json) = serde_json::to_string(&entry) {
+      let _ = fs::create_dir_all(&self.dir);
+      let _ = fs::write(path, json);
+    }
+  }
+
+  fn clear(&self) {
+    let Ok(files) = fs::read_dir(&self.dir) else {
+      return;
+    };
+    for file in files.flatten() {
+      let path = file.path();
+      if path.extension().is_some_and(|ext| ext == "json") {
+        let _ = fs::remove_file(path);
+      }
+    }
+  }
+}
+
+fn now() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map_or(0, |d| d.as_secs())
+}
+
+/// The URL with the `api_key` parameter redacted, fit to be written to
+/// disk.
+pub(crate) fn redacted(url: &str) -> String {
+  match reqwest::Url::parse(url) {
+    Ok(mut url) => {
+      redact_url(&mut url);
+      url.to_string()
+    }
+    Err(_) => url.to_string(),
+  }
+}
+
+/// Name the file after the word, the operation and the parameters, with a
+/// hash telling apart requests that only differ in characters not allowed
+/// in file names.
+pub(crate) fn file_name(url: &str) -> String {
+  let segments: Vec<&str> = url.rsplitn(3, '/').collect();
+  let key = match segments.as_slice() {
+    [operation, word, _] => format!("{}-{}", word, operation),
+    _ => url.to_string(),
+  };
+  let readable: String = key
+    .chars()
+    .map(|c| match c {
+      'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' => c,
+      _ => '_',
+    })
+    .collect();
+  format!("{}-{:016x}.json", readable, fnv1a(url))
+}
+
+/// A hash that, unlike std's, is guaranteed not to change between Rust
+/// releases, so stored file names stay valid.
+fn fnv1a(s: &str) -> u64 {
+  s.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+    (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+  })
+}
@@ -0,0 +1,507 @@
+//! Descriptions of API calls, shared by the blocking and async clients.
+//!
+//! Each endpoint is described once here: which operation it hits, which
+//! query parameters it sends and how its JSON response is turned into a
+//! typed value. The clients only differ in how the request is sent.
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::Value;
+
+use crate::builder::DEFAULT_MAX_RESPONSE_SIZE;
+use crate::retry::Retry;
+
+use crate::{
+  dedup_definitions, sort_definitions, Audio, Definition, DefinitionsQuery,
+  Etymology, Example, ExampleSearchResults, ExamplesQuery, Frequency,
+  FrequencyQuery, Operation, Paged, Phrase, Pronunciation, RelatedWords,
+  RelatedWordsQuery, RelationshipType, ScrabbleScore, Syllable, WordObject,
+  WordnikError, SUGGESTION_LIMIT,
+};
+
+/// Characters that can't appear literally in a path segment. The slash is
+/// included so that a word can never break out of its segment.
+const SEGMENT: &AsciiSet = &CONTROLS
+  .add(b' ')
+  .add(b'"')
+  .add(b'#')
+  .add(b'%')
+  .add(b'/')
+  .add(b'<')
+  .add(b'>')
+  .add(b'?')
+  .add(b'\\')
+  .add(b'`')
+  .add(b'{')
+  .add(b'}');
+
+/// Characters that can't appear literally in a query parameter value.
+const QUERY: &AsciiSet = &SEGMENT.add(b'&').add(b'+').add(b'=');
+
+/// Percent-encode a user-supplied path segment, such as the word.
+///
+/// `.` and `..` are rejected, since URL parsing would resolve them against
+/// the entry even when encoded.
+pub(crate) fn encode_segment(segment: &str) -> Result<String, WordnikError> {
+  if segment.is_empty() || segment == "." || segment == ".." {
+    return Err(WordnikError::InvalidWord(segment.to_string()));
+  }
+  Ok(utf8_percent_encode(segment, SEGMENT).to_string())
+}
+
+/// Trim `phrase` and collapse its runs of whitespace into single spaces.
+pub(crate) fn normalize_phrase(phrase: &str) -> String {
+  phrase.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn encode_query(value: &str) -> String {
+  utf8_percent_encode(value, QUERY).to_string()
+}
+
+/// The path of `operation` after the word.
+pub(crate) fn encode_operation(
+  operation: &Operation,
+) -> Result<String, WordnikError> {
+  match operation {
+    Operation::Custom(path) => path
+      .split('/')
+      .map(|segment| {
+        encode_segment(segment)
+          .map_err(|_| WordnikError::InvalidOperation(path.clone()))
+      })
+      .collect::<Result<Vec<_>, _>>()
+      .map(|segments| segments.join("/")),
+    operation => Ok(operation.to_string()),
+  }
+}
+
+type Parser<T> = Box<dyn FnOnce(Value) -> Result<T, WordnikError> + Send>;
+
+pub(crate) struct Call<T> {
+  word: String,
+  /// `None` for the word itself, at `{entry}{word}`.
+  operation: Option<Operation>,
+  params: Vec<(&'static str, String)>,
+  parse: Parser<T>,
+}
+
+impl<T> Call<T> {
+  fn new(
+    word: &str,
+    operation: Operation,
+    params: Vec<(&'static str, String)>,
+    parse: impl FnOnce(Value) -> Result<T, WordnikError> + Send + 'static,
+  ) -> Call<T> {
+    Call {
+      word: word.to_string(),
+      operation: Some(operation),
+      params,
+      parse: Box::new(parse),
+    }
+  }
+
+  pub(crate) fn parse(self, res: Value) -> Result<T, WordnikError> {
+    (self.parse)(res)
+  }
+
+  #[cfg(feature = "tracing")]
+  pub(crate) fn span(&self) -> tracing::Span {
+    tracing::debug_span!(
+      "wordnik",
+      operation = %self.operation.as_ref().map_or(String::new(), Operation::to_string),
+      word = %self.word,
+    )
+  }
+}
+
+/// The settings every request is built from.
+#[derive(Clone)]
+pub(crate) struct Config {
+  pub(crate) api_key: String,
+  pub(crate) entry: String,
+  /// Headers added with [WordnikBuilder::header](crate::WordnikBuilder::header).
+  pub(crate) extra_headers: HeaderMap,
+  pub(crate) retry: Retry,
+  /// The longest response body read, in bytes.
+  pub(crate) max_response_size: u64,
+}
+
+impl Config {
+  /// Validate the settings, making sure `entry` is an HTTP(S) URL ending
+  /// with a slash so that the word can be appended to it.
+  pub(crate) fn new(
+    api_key: String,
+    entry: String,
+  ) -> Result<Config, WordnikError> {
+    if api_key.is_empty() {
+      return Err(WordnikError::EmptyApiKey);
+    }
+    if HeaderValue::from_str(&api_key).is_err() {
+      return Err(WordnikError::InvalidApiKey);
+    }
+    if entry.is_empty() {
+      return Err(WordnikError::InvalidEntry("entry is empty".to_string()));
+    }
+    let entry = Config::normalize(entry);
+    let url = reqwest::Url::parse(&entry)
+      .map_err(|e| WordnikError::InvalidEntry(e.to_string()))?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+      return Err(WordnikError::InvalidEntry(format!(
+        "unsupported scheme {}",
+        url.scheme()
+      )));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+      return Err(WordnikError::InvalidEntry(
+        "entry must not have a query or fragment".to_string(),
+      ));
+    }
+    Ok(Config {
+      api_key,
+      entry,
+      extra_headers: HeaderMap::new(),
+      retry: Retry::default(),
+      max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+    })
+  }
+
+  /// Build the settings without validation, only appending the trailing
+  /// slash if it is missing.
+  pub(crate) fn new_unchecked(api_key: String, entry: String) -> Config {
+    Config {
+      api_key,
+      entry: Config::normalize(entry),
+      extra_headers: HeaderMap::new(),
+      retry: Retry::default(),
+      max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+    }
+  }
+
+  fn normalize(entry: String) -> String {
+    if entry.ends_with('/') {
+      entry
+    } else {
+      entry + "/"
+    }
+  }
+
+  pub(crate) fn url<T>(&self, call: &Call<T>) -> Result<String, WordnikError> {
+    self.url_for(&call.word, call)
+  }
+
+  /// The key of the response to `call` in the caches: its URL, but with
+  /// the word trimmed and lowercased, so that "Run" and "run " share the
+  /// response to "run". The operation and the parameters are kept. Words
+  /// that are only valid untrimmed keep their URL.
+  pub(crate) fn cache_key<T>(
+    &self,
+    call: &Call<T>,
+  ) -> Result<String, WordnikError> {
+    self
+      .url_for(&call.word.trim().to_lowercase(), call)
+      .or_else(|_| self.url(call))
+  }
+
+  fn url_for<T>(
+    &self,
+    word: &str,
+    call: &Call<T>,
+  ) -> Result<String, WordnikError> {
+    let mut url = self.entry.clone() + &encode_segment(word)?;
+    if let Some(operation) = &call.operation {
+      url = url + "/" + &encode_operation(operation)?;
+    }
+    Ok(
+      call
+        .params
+        .iter()
+        .enumerate()
+        .fold(url, |acc, (i, (key, value))| {
+          acc
+            + if i == 0 { "?" } else { "&" }
+            + key
+            + "="
+            + &encode_query(value)
+        }),
+    )
+  }
+
+  /// The headers sent with every request, which carry the API key. Extra
+  /// headers can't replace it.
+  pub(crate) fn headers(&self) -> Result<HeaderMap, WordnikError> {
+    let mut api_key = HeaderValue::from_str(&self.api_key)
+      .map_err(|_| WordnikError::InvalidApiKey)?;
+    api_key.set_sensitive(true);
+    let mut headers = self.extra_headers.clone();
+    headers.insert("api_key", api_key);
+    Ok(headers)
+  }
+}
+
+pub(crate) fn word(word: &str) -> Call<WordObject> {
+  Call {
+    word: word.to_string(),
+    operation: None,
+    params: vec![
+      ("useCanonical", "true".to_string()),
+      ("includeSuggestions", "true".to_string()),
+    ],
+    parse: Box::new(|res| Ok(serde_json::from_value(res)?)),
+  }
+}
+
+/// The suggestions for `word`, up to [SUGGESTION_LIMIT], without `word`
+/// itself or repeats, in any case; the first spelling of a repeat is
+/// kept.
+pub(crate) fn suggestions(found: WordObject, word: &str) -> Vec<String> {
+  let mut seen = std::collections::HashSet::new();
+  let mut suggestions = single_words_except(found.suggestions, word);
+  suggestions.retain(|s| seen.insert(s.trim().to_lowercase()));
+  suggestions.truncate(SUGGESTION_LIMIT);
+  suggestions
+}
+
+pub(crate) fn definitions(
+  word: &str,
+  query: &DefinitionsQuery,
+) -> Call<Vec<Definition>> {
+  let dedup = query.dedup;
+  let sort = query.sort.clone();
+  let include_empty = query.include_empty;
+  Call::new(word, Operation::Definitions, query.params(), move |res| {
+    let mut definitions: Vec<Definition> = serde_json::from_value(res)?;
+    if !include_empty {
+      definitions
+        .retain(|def| def.definition.as_ref().is_some_and(|t| !t.is_empty()));
+    }
+    if dedup {
+      dedup_definitions(&mut definitions);
+    }
+    sort_definitions(&mut definitions, &sort);
+    Ok(definitions)
+  })
+}
+
+/// A definitions lookup asking for a single entry, for checking that the
+/// word is known.
+pub(crate) fn word_exists(word: &str) -> Call<bool> {
+  let params = vec![("limit", "1".to_string())];
+  Call::new(word, Operation::Definitions, params, |res| {
+    let definitions: Vec<Value> = serde_json::from_value(res)?;
+    Ok(!definitions.is_empty())
+  })
+}
+
+/// A definitions lookup asking for a single entry of the word's canonical
+/// form, for its headword. No entry means the word is unknown.
+pub(crate) fn canonical(word: &str) -> Call<String> {
+  let params = vec![
+    ("useCanonical", "true".to_string()),
+    ("limit", "1".to_string()),
+  ];
+  Call::new(word, Operation::Definitions, params, |res| {
+    let definitions: Vec<Value> = serde_json::from_value(res)?;
+    definitions
+      .first()
+      .and_then(|def| def["word"].as_str())
+      .map(str::to_string)
+      .ok_or(WordnikError::NotFound)
+  })
+}
+
+pub(crate) fn pronunciations(word: &str) -> Call<Vec<Pronunciation>> {
+  Call::new(word, Operation::Pronunciations, Vec::new(), |res| {
+    let mut pronunciations: Vec<Pronunciation> = serde_json::from_value(res)?;
+    pronunciations.sort_by_key(|p| p.seq);
+    Ok(pronunciations)
+  })
+}
+
+pub(crate) fn audio(word: &str) -> Call<Vec<Audio>> {
+  Call::new(word, Operation::Audio, Vec::new(), |res| {
+    Ok(serde_json::from_value(res)?)
+  })
+}
+
+pub(crate) fn examples(
+  word: &str,
+  query: &ExamplesQuery,
+) -> Call<Paged<Example>> {
+  Call::new(word, Operation::Examples, query.params(), |res| {
+    let results: ExampleSearchResults = serde_json::from_value(res)?;
+    let total = results.total_results.unwrap_or(results.examples.len());
+    Ok(Paged {
+      items: results.examples,
+      total,
+    })
+  })
+}
+
+pub(crate) fn top_example(word: &str) -> Call<Example> {
+  Call::new(word, Operation::TopExample, Vec::new(), |res| {
+    Ok(serde_json::from_value(res)?)
+  })
+}
+
+/// The text of `example`, unless it is empty.
+pub(crate) fn example_text(example: Example) -> Option<String> {
+  let text = example.plain_text();
+  (!text.trim().is_empty()).then_some(text)
+}
+
+pub(crate) fn ipa_only(pronunciations: Vec<Pronunciation>) -> Vec<String> {
+  pronunciations
+    .into_iter()
+    .filter(|p| p.raw_type == "IPA")
+    .map(|p| p.raw)
+    .collect()
+}
+
+/// The most frequent non-empty part of speech of `definitions`, the first
+/// to appear among the most frequent ones on a tie.
+pub(crate) fn primary_part_of_speech(
+  definitions: Vec<Definition>,
+) -> Option<String> {
+  let mut counts: Vec<(String, usize)> = Vec::new();
+  for part_of_speech in definitions.into_iter().filter_map(|d| d.part_of_speech)
+  {
+    if part_of_speech.is_empty() {
+      continue;
+    }
+    match counts.iter_mut().find(|(p, _)| *p == part_of_speech) {
+      Some((_, count)) => *count += 1,
+      None => counts.push((part_of_speech, 1)),
+    }
+  }
+  let max = counts.iter().map(|(_, count)| *count).max()?;
+  counts
+    .into_iter()
+    .find(|(_, count)| *count == max)
+    .map(|(part_of_speech, _)| part_of_speech)
+}
+
+pub(crate) fn related_words(
+  word: &str,
+  query: &RelatedWordsQuery,
+) -> Call<Vec<RelatedWords>> {
+  Call::new(word, Operation::RelatedWords, query.params(), |res| {
+    Ok(serde_json::from_value(res)?)
+  })
+}
+
+/// The related words lookup behind `get_synonyms_antonyms`, where a 404
+/// just means there are none.
+pub(crate) fn synonyms_antonyms(
+  word: &str,
+) -> Call<(Vec<String>, Vec<String>)> {
+  let query = RelatedWordsQuery::new().relationship_types(&[
+    RelationshipType::Synonym,
+    RelationshipType::Antonym,
+  ]);
+  Call::new(word, Operation::RelatedWords, query.params(), |res| {
+    let groups: Vec<RelatedWords> = serde_json::from_value(res)?;
+    let (mut synonyms, mut antonyms) = (Vec::new(), Vec::new());
+    for group in groups {
+      match group.relationship_type {
+        RelationshipType::Synonym => synonyms.extend(group.words),
+        RelationshipType::Antonym => antonyms.extend(group.words),
+        _ => {}
+      }
+    }
+    Ok((synonyms, antonyms))
+  })
+}
+
+/// The words of one relationship type, in order and without repeats.
+pub(crate) fn related_of(
+  word: &str,
+  relationship_type: RelationshipType,
+  limit: Option<u32>,
+) -> Call<Vec<String>> {
+  let mut query =
+    RelatedWordsQuery::new().relationship_types(&[relationship_type]);
+  if let Some(limit) = limit {
+    query = query.limit_per_relationship_type(limit);
+  }
+  Call::new(word, Operation::RelatedWords, query.params(), move |res| {
+    let groups: Vec<RelatedWords> = serde_json::from_value(res)?;
+    let mut words: Vec<String> = Vec::new();
+    for group in groups {
+      if group.relationship_type != relationship_type {
+        continue;
+      }
+      for word in group.words {
+        if !words.contains(&word) {
+          words.push(word);
+        }
+      }
+    }
+    Ok(words)
+  })
+}
+
+/// Treat [NotFound](WordnikError::NotFound) as nothing found, for the
+/// sections of a report that a word may lack, such as examples.
+pub(crate) fn or_empty<T: Default>(
+  res: Result<T, WordnikError>,
+) -> Result<T, WordnikError> {
+  match res {
+    Err(WordnikError::NotFound) => Ok(T::default()),
+    res => res,
+  }
+}
+
+/// Drop the phrases in `words`, and `word` itself in any case.
+pub(crate) fn single_words_except(
+  words: Vec<String>,
+  word: &str,
+) -> Vec<String> {
+  let word = word.trim().to_lowercase();
+  words
+    .into_iter()
+    .filter(|w| !w.trim().contains(char::is_whitespace))
+    .filter(|w| w.trim().to_lowercase() != word)
+    .collect()
+}
+
+pub(crate) fn frequency(word: &str, query: &FrequencyQuery) -> Call<Frequency> {
+  Call::new(word, Operation::Frequency, query.params(), |res| {
+    Ok(serde_json::from_value(res)?)
+  })
+}
+
+pub(crate) fn hyphenation(word: &str) -> Call<Vec<Syllable>> {
+  Call::new(word, Operation::Hyphenation, Vec::new(), |res| {
+    let mut syllables: Vec<Syllable> = serde_json::from_value(res)?;
+    syllables.sort_by_key(|s| s.seq);
+    Ok(syllables)
+  })
+}
+
+pub(crate) fn phrases(word: &str) -> Call<Vec<Phrase>> {
+  Call::new(word, Operation::Phrases, Vec::new(), |res| {
+    Ok(serde_json::from_value(res)?)
+  })
+}
+
+pub(crate) fn etymologies(word: &str) -> Call<Vec<Etymology>> {
+  Call::new(word, Operation::Etymologies, Vec::new(), |res| {
+    let etymologies: Vec<String> = serde_json::from_value(res)?;
+    Ok(
+      etymologies
+        .iter()
+        .map(|raw| Etymology::parse(raw))
+        .collect(),
+    )
+  })
+}
+
+pub(crate) fn scrabble_score(word: &str) -> Call<ScrabbleScore> {
+  Call::new(word, Operation::ScrabbleScore, Vec::new(), |res| {
+    Ok(serde_json::from_value(res)?)
+  })
+}
+
+pub(crate) fn raw(word: &str, operation: Operation) -> Call<Value> {
+  Call::new(word, operation, Vec::new(), Ok)
+}
@@ -0,0 +1,66 @@
+//! Lenient deserializers for fields the API doesn't send consistently.
+
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::str::FromStr;
+
+use crate::Timestamp;
+
+/// Accept a number, a string holding a number, or `null`. Anything that
+/// doesn't parse becomes the default value instead of an error.
+pub(crate) fn lenient_number<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+  D: Deserializer<'de>,
+  T: FromStr + Default,
+{
+  let value = Value::deserialize(deserializer)?;
+  let parsed = match value {
+    Value::Number(n) => n.to_string().parse().ok(),
+    Value::String(s) => s.trim().parse().ok(),
+    _ => None,
+  };
+  Ok(parsed.unwrap_or_default())
+}
+
+/// Accept a value or `null`, which becomes the default value.
+pub(crate) fn nullable<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+  D: Deserializer<'de>,
+  T: Deserialize<'de> + Default,
+{
+  Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Accept a string or `null`, trimming the string and collapsing runs of
+/// whitespace inside it to a single space.
+pub(crate) fn collapsed_text<'de, D>(
+  deserializer: D,
+) -> Result<Option<String>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let text = Option::<String>::deserialize(deserializer)?;
+  Ok(text.map(|text| text.split_whitespace().collect::<Vec<_>>().join(" ")))
+}
+
+/// Accept a timestamp string or `null`. With the `chrono` feature, it is
+/// parsed as RFC 3339 or as the API's own format, whose offset lacks a
+/// colon, and becomes `None` if it is neither.
+pub(crate) fn timestamp<'de, D>(
+  deserializer: D,
+) -> Result<Option<Timestamp>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let text = Option::<String>::deserialize(deserializer)?;
+  #[cfg(feature = "chrono")]
+  let text = text.and_then(|text| {
+    chrono::DateTime::parse_from_rfc3339(&text)
+      .or_else(|_| {
+        chrono::DateTime::parse_from_str(&text, "%Y-%m-%dT%H:%M:%S%.f%z")
+      })
+      .ok()
+      .map(|time| time.with_timezone(&chrono::Utc))
+  });
+  Ok(text)
+}
@@ -0,0 +1,145 @@
+use std::fmt::Display;
+
+/// The error type returned by all API calls.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WordnikError {
+  /// The HTTP request could not be completed.
+  Http(reqwest::Error),
+  /// The response body was not the JSON we expected.
+  Json(serde_json::Error),
+  /// The API key is empty.
+  EmptyApiKey,
+  /// The API key contains characters that can't be sent in a header.
+  InvalidApiKey,
+  /// The entry URL is not usable; the reason is attached.
+  InvalidEntry(String),
+  /// A header added with
+  /// [WordnikBuilder::header](crate::WordnikBuilder::header) has an
+  /// invalid name or value; the name is attached.
+  InvalidHeader(String),
+  /// The word can't be used in a request, e.g. because it is empty.
+  InvalidWord(String),
+  /// A [Custom](crate::Operation::Custom) operation has an empty, `.` or
+  /// `..` segment; the path is attached.
+  InvalidOperation(String),
+  /// A required environment variable is not set.
+  MissingEnvVar(&'static str),
+  /// The API answered 404, i.e. it doesn't know the word.
+  NotFound,
+  /// The API answered 429 Too Many Requests; the wait it asked for in the
+  /// `Retry-After` header is attached, if it sent one.
+  TooManyRequests(Option<std::time::Duration>),
+  /// The response is not cached, and the client is
+  /// [offline](crate::WordnikBuilder::offline).
+  Offline,
+  /// The response body is longer than
+  /// [WordnikBuilder::max_response_size](crate::WordnikBuilder::max_response_size);
+  /// the limit in bytes is attached.
+  ResponseTooLarge(u64),
+  /// A file could not be read or written, e.g. by
+  /// [WordDetails::save_to](crate::WordDetails::save_to).
+  File(std::path::PathBuf, std::io::Error),
+  /// Writing the output failed, e.g. in
+  /// [write_jsonl](crate::write_jsonl) because the pipe was closed.
+  Io(std::io::Error),
+  /// A recorded response could not be read or written, or is missing in
+  /// replay mode.
+  #[cfg(feature = "fixtures")]
+  Fixture(std::path::PathBuf, std::io::Error),
+}
+
+impl Display for WordnikError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match &self {
+      WordnikError::Http(e) => write!(f, "request failed: {}", e),
+      WordnikError::Json(e) => write!(f, "invalid response: {}", e),
+      WordnikError::EmptyApiKey => write!(f, "API key is empty"),
+      WordnikError::InvalidApiKey => {
+        write!(f, "API key contains characters not allowed in a header")
+      }
+      WordnikError::InvalidEntry(reason) => {
+        write!(f, "invalid entry URL: {}", reason)
+      }
+      WordnikError::InvalidHeader(name) => {
+        write!(f, "invalid header {:?}", name)
+      }
+      WordnikError::InvalidWord(word) => write!(f, "invalid word {:?}", word),
+      WordnikError::InvalidOperation(path) => {
+        write!(f, "invalid operation {:?}", path)
+      }
+      WordnikError::MissingEnvVar(name) => {
+        write!(f, "environment variable {} is not set", name)
+      }
+      WordnikError::NotFound => write!(f, "word not found"),
+      WordnikError::TooManyRequests(Some(wait)) => {
+        write!(f, "too many requests, retry after {:?}", wait)
+      }
+      WordnikError::TooManyRequests(None) => write!(f, "too many requests"),
+      WordnikError::Offline => write!(f, "response not cached while offline"),
+      WordnikError::ResponseTooLarge(max) => {
+        write!(f, "response larger than {} bytes", max)
+      }
+      WordnikError::File(path, e) => write!(f, "{}: {}", path.display(), e),
+      WordnikError::Io(e) => write!(f, "write failed: {}", e),
+      #[cfg(feature = "fixtures")]
+      WordnikError::Fixture(path, e) => {
+        write!(f, "fixture {}: {}", path.display(), e)
+      }
+    }
+  }
+}
+
+impl std::error::Error for WordnikError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match &self {
+      WordnikError::Http(e) => Some(e),
+      WordnikError::Json(e) => Some(e),
+      WordnikError::File(_, e) => Some(e),
+      WordnikError::Io(e) => Some(e),
+      #[cfg(feature = "fixtures")]
+      WordnikError::Fixture(_, e) => Some(e),
+      _ => None,
+    }
+  }
+}
+
+impl From<reqwest::Error> for WordnikError {
+  fn from(mut e: reqwest::Error) -> Self {
+    if let Some(url) = e.url_mut() {
+      redact_url(url);
+    }
+    WordnikError::Http(e)
+  }
+}
+
+/// Replace the value of the `api_key` query parameter, so the key doesn't
+/// leak into error messages and logs.
+pub(crate) fn redact_url(url: &mut reqwest::Url) {
+  if !url.query_pairs().any(|(key, _)| key == "api_key") {
+    return;
+  }
+  let pairs: Vec<(String, String)> = url
+    .query_pairs()
+    .map(|(key, value)| {
+      if key == "api_key" {
+        (key.into_owned(), "REDACTED".to_string())
+      } else {
+        (key.into_owned(), value.into_owned())
+      }
+    })
+    .collect();
+  url.query_pairs_mut().clear().extend_pairs(pairs);
+}
+
+/// Shorten an API key to its first few characters for display.
+pub(crate) fn redact_key(api_key: &str) -> String {
+  let prefix: String = api_key.chars().take(4).collect();
+  prefix + "…"
+}
+
+impl From<serde_json::Error> for WordnikError {
+  fn from(e: serde_json::Error) -> Self {
+    WordnikError::Json(e)
+  }
+}
@@ -0,0 +1,151 @@
+//! Parsing the markup of the etymologies endpoint.
+
+use serde::{Deserialize, Serialize};
+
+use crate::pretty::{decode_entities, strip_tags};
+
+/// One piece of an [Etymology].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum EtymologySegment {
+  /// The name of a language, e.g. "Middle English", from `<ets>`.
+  Language(String),
+  /// A word the word comes from, e.g. "worden", from `<er>`.
+  Etymon(String),
+  /// Anything else, e.g. ", from ".
+  Text(String),
+}
+
+impl EtymologySegment {
+  fn text(&self) -> &str {
+    match self {
+      EtymologySegment::Language(s)
+      | EtymologySegment::Etymon(s)
+      | EtymologySegment::Text(s) => s,
+    }
+  }
+}
+
+/// The history of a word, as returned by
+/// [get_etymologies](crate::Wordnik::get_etymologies).
+///
+/// # Example
+///
+/// ```
+/// use wordnik::{Etymology, EtymologySegment};
+///
+/// let ety = Etymology::parse(
+///   "<ety>[<ets>Middle English</ets> <er>worden</er>, from <ets>Old English</ets> <er>word</er>.]</ety>",
+/// );
+/// assert_eq!(ety.segments[0], EtymologySegment::Language("Middle English".to_string()));
+/// assert_eq!(ety.segments[2], EtymologySegment::Etymon("worden".to_string()));
+/// assert_eq!(ety.languages().collect::<Vec<_>>(), ["Middle English", "Old English"]);
+/// assert_eq!(ety.to_plain_text(), "Middle English worden, from Old English word.");
+///
+/// // A tag that is never closed is dropped, keeping its text; the tags
+/// // after it still parse.
+/// let ety = Etymology::parse("[From <ets>Latin <er>verbum</er>.]");
+/// assert_eq!(
+///   ety.segments,
+///   [
+///     EtymologySegment::Text("From Latin ".to_string()),
+///     EtymologySegment::Etymon("verbum".to_string()),
+///     EtymologySegment::Text(".".to_string()),
+///   ],
+/// );
+/// assert_eq!(ety.languages().count(), 0);
+/// assert_eq!(ety.to_plain_text(), "From Latin verbum.");
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Etymology {
+  /// The text as the API sent it.
+  pub raw: String,
+  pub segments: Vec<EtymologySegment>,
+}
+
+impl Etymology {
+  /// Split `raw` into segments. This never fails: unknown tags, and
+  /// `<ets>` or `<er>` without a closing tag, are dropped, keeping their
+  /// text as [Text](EtymologySegment::Text), and a `<` that doesn't start
+  /// a tag is kept as text.
+  pub fn parse(raw: &str) -> Etymology {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut rest = raw;
+    while let Some(start) = rest.find('<') {
+      text.push_str(&rest[..start]);
+      rest = &rest[start..];
+      let segment = [("<ets>", "</ets>"), ("<er>", "</er>")]
+        .iter()
+        .find(|(open, _)| rest.starts_with(open))
+        .and_then(|(open, close)| {
+          let end = rest.find(close)?;
+          let inner = strip_tags(&rest[open.len()..end]);
+          let segment = match *open {
+            "<ets>" => EtymologySegment::Language(decode_entities(&inner)),
+            _ => EtymologySegment::Etymon(decode_entities(&inner)),
+          };
+          Some((segment, end + close.len()))
+        });
+      match segment {
+        Some((segment, len)) => {
+          push_text(&mut segments, &mut text);
+          segments.push(segment);
+          rest = &rest[len..];
+        }
+        None => match rest.find('>') {
+          Some(end) if rest[1..].starts_with(tag_start) => {
+            rest = &rest[end + 1..];
+          }
+          _ => {
+            text.push('<');
+            rest = &rest[1..];
+          }
+        },
+      }
+    }
+    text.push_str(rest);
+    push_text(&mut segments, &mut text);
+    trim_brackets(&mut segments);
+    Etymology {
+      raw: raw.to_string(),
+      segments,
+    }
+  }
+
+  /// The languages, in order.
+  pub fn languages(&self) -> impl Iterator<Item = &str> {
+    self.segments.iter().filter_map(|segment| match segment {
+      EtymologySegment::Language(language) => Some(language.as_str()),
+      _ => None,
+    })
+  }
+
+  /// The text without markup or the surrounding brackets.
+  pub fn to_plain_text(&self) -> String {
+    let text: String = self.segments.iter().map(|s| s.text()).collect();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+  }
+}
+
+fn tag_start(c: char) -> bool {
+  c == '/' || c.is_ascii_alphabetic()
+}
+
+fn push_text(segments: &mut Vec<EtymologySegment>, text: &mut String) {
+  if !text.is_empty() {
+    segments.push(EtymologySegment::Text(decode_entities(text)));
+    text.clear();
+  }
+}
+
+/// Drop the brackets etymologies are wrapped in.
+fn trim_brackets(segments: &mut Vec<EtymologySegment>) {
+  if let Some(EtymologySegment::Text(first)) = segments.first_mut() {
+    *first = first.trim_start().trim_start_matches('[').to_string();
+  }
+  if let Some(EtymologySegment::Text(last)) = segments.last_mut() {
+    *last = last.trim_end().trim_end_matches(']').to_string();
+  }
+  segments.retain(|s| !matches!(s, EtymologySegment::Text(t) if t.is_empty()));
+}
@@ -0,0 +1,282 @@
+//! Exporting results for spreadsheets, data frames and pipelines.
+
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::{Definition, Example, WordnikError};
+
+const COLUMNS: [&str; 5] = [
+  "word",
+  "part_of_speech",
+  "definition",
+  "source_dictionary",
+  "attribution_text",
+];
+
+/// Export `definitions` as CSV, with a header row and the columns word,
+/// part_of_speech, definition, source_dictionary and attribution_text.
+///
+/// Fields with commas, quotes or line breaks are quoted, doubling their
+/// quotes, as described in RFC 4180. Rows end with `\n`, and a missing
+/// text or part of speech is an empty field. The text is exported as
+/// the API sent it; use [Definition::plain_text] first for text without
+/// markup.
+///
+/// # Example
+///
+/// ```
+/// use wordnik::{to_csv, Definition};
+///
+/// let def = Definition {
+///   word: "say".to_string(),
+///   definition: Some("To utter, e.g. \"hello\".\nAlso: to state.".to_string()),
+///   part_of_speech: Some("verb".to_string()),
+///   source_dictionary: "ahd-5".to_string(),
+///   ..Definition::default()
+/// };
+/// assert_eq!(
+///   to_csv(&[def]),
+///   "word,part_of_speech,definition,source_dictionary,attribution_text\n\
+///    say,verb,\"To utter, e.g. \"\"hello\"\".\nAlso: to state.\",ahd-5,\n",
+/// );
+/// ```
+pub fn to_csv(definitions: &[Definition]) -> String {
+  to_delimited(definitions, ',')
+}
+
+/// Export `definitions` as tab-separated values, quoted like
+/// [to_csv].
+pub fn to_tsv(definitions: &[Definition]) -> String {
+  to_delimited(definitions, '\t')
+}
+
+/// Export `definitions` like [to_csv], separating fields with
+/// `delimiter`, e.g. `';'`.
+///
+/// # Example
+///
+/// ```
+/// use wordnik::{to_delimited, Definition};
+///
+/// let def = Definition {
+///   word: "run".to_string(),
+///   definition: Some("To go; to flee.".to_string()),
+///   ..Definition::default()
+/// };
+/// let csv = to_delimited(&[def], ';');
+/// assert_eq!(csv.lines().nth(1), Some("run;;\"To go; to flee.\";;"));
+/// ```
+pub fn to_delimited(definitions: &[Definition], delimiter: char) -> String {
+  let mut out = String::new();
+  write_row(&mut out, &COLUMNS, delimiter);
+  for def in definitions {
+    let row = [
+      def.word.as_str(),
+      def.part_of_speech.as_deref().unwrap_or_default(),
+      def.definition.as_deref().unwrap_or_default(),
+      def.source_dictionary.as_str(),
+      def.attribution_text.as_str(),
+    ];
+    write_row(&mut out, &row, delimiter);
+  }
+  out
+}
+
+fn write_row(out: &mut String, fields: &[&str], delimiter: char) {
+  for (i, field) in fields.iter().enumerate() {
+    if i > 0 {
+      out.push(delimiter);
+    }
+    let quote = field
+      .chars()
+      .any(|c| c == delimiter || c == '"' || c == '\n' || c == '\r');
+    if quote {
+      out.push('"');
+      out.push_str(&field.replace('"', "\"\""));
+      out.push('"');
+    } else {
+      out.push_str(field);
+    }
+  }
+  out.push('\n');
+}
+
+/// Export `items`, e.g. definitions, examples or related words, as JSON
+/// Lines: one compact JSON object per line, each ending with `\n`.
+/// Line breaks in the text are escaped, so every line is a whole item.
+///
+/// # Example
+///
+/// ```
+/// use wordnik::{to_jsonl, Definition, Example};
+///
+/// let def = Definition {
+///   word: "run".to_string(),
+///   definition: Some("To move.\nTo go.".to_string()),
+///   ..Definition::default()
+/// };
+/// let jsonl = to_jsonl(&[def.clone(), def.clone()]).unwrap();
+/// let lines: Vec<&str> = jsonl.lines().collect();
+/// assert_eq!(lines.len(), 2);
+/// let parsed: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+/// assert_eq!(parsed["text"], "To move.\nTo go.");
+///
+/// let examples = [Example { text: "They run.".to_string(), ..Example::default() }];
+/// assert!(to_jsonl(&examples).unwrap().ends_with("}\n"));
+/// ```
+pub fn to_jsonl<T: Serialize>(items: &[T]) -> Result<String, WordnikError> {
+  let mut out = Vec::new();
+  write_jsonl(&mut out, items)?;
+  Ok(String::from_utf8(out).expect("serde_json writes UTF-8"))
+}
+
+/// Write `items` to `writer` as JSON Lines, like [to_jsonl], e.g. to
+/// stdout for `jq`.
+///
+/// # Example
+///
+/// ```no_run
+/// use wordnik::{write_jsonl, Wordnik};
+///
+/// let api = Wordnik::new(
+///   "YOUR_API_KEY".to_string(),
+///   "https://api.wordnik.com/v4/word.json/".to_string(),
+/// );
+/// let definitions = api.get_definitions("word").unwrap();
+/// write_jsonl(std::io::stdout().lock(), &definitions).unwrap();
+/// ```
+///
+/// A failing writer is reported as [Io](WordnikError::Io):
+///
+/// ```
+/// use std::io::{self, Write};
+/// use wordnik::{write_jsonl, Definition, WordnikError};
+///
+/// struct ClosedPipe;
+///
+/// impl Write for ClosedPipe {
+///   fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+///     Err(io::ErrorKind::BrokenPipe.into())
+///   }
+///
+///   fn flush(&mut self) -> io::Result<()> {
+///     Ok(())
+///   }
+/// }
+///
+/// let err = write_jsonl(ClosedPipe, &[Definition::default()]).unwrap_err();
+/// assert!(matches!(err, WordnikError::Io(e) if e.kind() == io::ErrorKind::BrokenPipe));
+/// ```
+pub fn write_jsonl<W: Write, T: Serialize>(
+  mut writer: W,
+  items: &[T],
+) -> Result<(), WordnikError> {
+  for item in items {
+    serde_json::to_writer(&mut writer, item).map_err(|e| match e.is_io() {
+      true => WordnikError::Io(e.into()),
+      false => WordnikError::Json(e),
+    })?;
+    writer.write_all(b"\n").map_err(WordnikError::Io)?;
+  }
+  Ok(())
+}
+
+/// A row of a TSV file for Anki's "Import File": the word on the front,
+/// the first `max_definitions` definitions with text, and `example` if
+/// any, as HTML on the back, and the parts of speech as tags. Tags are
+/// separated by spaces, so spaces within one become `_`.
+///
+/// Text is taken without markup and HTML-escaped, and tabs and line
+/// breaks are replaced so the row stays one line of three fields. When
+/// importing, tick "Allow HTML in fields" and map the third field to
+/// Tags.
+///
+/// # Example
+///
+/// ```
+/// use wordnik::{anki_note, Definition, Example};
+///
+/// let def = |text: &str, pos: &str| Definition {
+///   word: "set".to_string(),
+///   definition: Some(text.to_string()),
+///   part_of_speech: Some(pos.to_string()),
+///   ..Definition::default()
+/// };
+/// let definitions = [
+///   def("To put <i>in</i> place.", "verb-transitive"),
+///   def("A group of\tthings & <b>the like</b>.", "noun"),
+///   def("Fixed.", "adjective"),
+/// ];
+/// let example = Example { text: "Set it down.\nGently.".to_string(), ..Example::default() };
+/// assert_eq!(
+///   anki_note("set", &definitions, Some(&example), 2),
+///   "set\t\
+///    <ol><li><i>verb-transitive</i> To put in place.</li>\
+///    <li><i>noun</i> A group of things &amp; the like.</li></ol>\
+///    <p><i>Set it down.<br>Gently.</i></p>\t\
+///    verb-transitive noun\n",
+/// );
+/// ```
+pub fn anki_note(
+  word: &str,
+  definitions: &[Definition],
+  example: Option<&Example>,
+  max_definitions: usize,
+) -> String {
+  let mut back = String::new();
+  let mut tags: Vec<String> = Vec::new();
+  let mut items = definitions
+    .iter()
+    .map(|def| (def, def.plain_text()))
+    .filter(|(_, text)| !text.trim().is_empty())
+    .take(max_definitions)
+    .peekable();
+  if items.peek().is_some() {
+    back.push_str("<ol>");
+    for (def, text) in items {
+      back.push_str("<li>");
+      if let Some(pos) = def.part_of_speech.as_deref() {
+        back.push_str(&format!("<i>{}</i> ", escape_html(pos)));
+        let tag = pos.split_whitespace().collect::<Vec<_>>().join("_");
+        if !tag.is_empty() && !tags.contains(&tag) {
+          tags.push(tag);
+        }
+      }
+      back.push_str(&escape_html(&text));
+      back.push_str("</li>");
+    }
+    back.push_str("</ol>");
+  }
+  if let Some(example) = example {
+    back.push_str(&format!(
+      "<p><i>{}</i></p>",
+      escape_html(&example.plain_text())
+    ));
+  }
+  let fields = [escape_html(word), back, escape_html(&tags.join(" "))];
+  let mut row = fields.join("\t");
+  row.push('\n');
+  row
+}
+
+/// Escape `text` for an HTML field of an Anki note, turning line breaks
+/// into `<br>` and tabs into spaces.
+fn escape_html(text: &str) -> String {
+  let mut out = String::with_capacity(text.len());
+  let mut chars = text.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '&' => out.push_str("&amp;"),
+      '<' => out.push_str("&lt;"),
+      '>' => out.push_str("&gt;"),
+      '"' => out.push_str("&quot;"),
+      '\'' => out.push_str("&#39;"),
+      '\t' => out.push(' '),
+      '\r' if chars.peek() == Some(&'\n') => {}
+      '\r' | '\n' => out.push_str("<br>"),
+      c => out.push(c),
+    }
+  }
+  out
+}
@@ -0,0 +1,103 @@
+//! Recording responses to disk and replaying them, for tests that should
+//! run without network access or an API key.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::cache::{file_name, redacted};
+use crate::{Requester, WordnikError};
+
+/// How [with_fixture_dir](crate::Wordnik::with_fixture_dir) treats a
+/// request without a recorded response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FixtureMode {
+  /// Send it and record the response.
+  Record,
+  /// Fail with [WordnikError::Fixture], so that nothing goes online.
+  Replay,
+}
+
+/// One recorded response. The URL is only kept for humans reading the
+/// file, with the API key redacted.
+#[derive(Serialize, Deserialize)]
+struct Cassette {
+  url: String,
+  status: u16,
+  #[serde(default)]
+  body: Value,
+}
+
+pub(crate) struct Fixtures {
+  dir: PathBuf,
+  mode: FixtureMode,
+  inner: Arc<dyn Requester>,
+}
+
+impl Fixtures {
+  pub(crate) fn new(
+    dir: PathBuf,
+    mode: FixtureMode,
+    inner: Arc<dyn Requester>,
+  ) -> Fixtures {
+    Fixtures { dir, mode, inner }
+  }
+
+  fn record(
+    &self,
+    path: &PathBuf,
+    cassette: &Cassette,
+  ) -> Result<(), WordnikError> {
+    let fixture_error = |e| WordnikError::Fixture(path.clone(), e);
+    fs::create_dir_all(&self.dir).map_err(fixture_error)?;
+    let json = serde_json::to_string_pretty(cassette)?;
+    fs::write(path, json + "\n").map_err(fixture_error)
+  }
+}
+
+impl Requester for Fixtures {
+  fn request(
+    &self,
+    url: &str,
+    headers: &HeaderMap,
+  ) -> Result<Value, WordnikError> {
+    let redacted = redacted(url);
+    let path = self.dir.join(file_name(&redacted));
+    match fs::read_to_string(&path) {
+      Ok(json) => {
+        let cassette: Cassette = serde_json::from_str(&json)?;
+        return match cassette.status {
+          404 => Err(WordnikError::NotFound),
+          _ => Ok(cassette.body),
+        };
+      }
+      Err(e) if e.kind() != io::ErrorKind::NotFound => {
+        return Err(WordnikError::Fixture(path, e));
+      }
+      Err(e) if self.mode == FixtureMode::Replay => {
+        return Err(WordnikError::Fixture(path, e));
+      }
+      Err(_) => {}
+    }
+
+    let res = self.inner.request(url, headers);
+    let (status, body) = match &res {
+      Ok(body) => (200, body.clone()),
+      Err(WordnikError::NotFound) => (404, Value::Null),
+      Err(_) => return res,
+    };
+    let cassette = Cassette {
+      url: redacted,
+      status,
+      body,
+    };
+    self.record(&path, &cassette)?;
+    res
+  }
+}
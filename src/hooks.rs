@@ -0,0 +1,115 @@
+//! Callbacks run around every request, registered on the builder.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+use crate::{Fetched, WordnikError};
+
+/// A request about to be sent, as seen by
+/// [on_request](crate::WordnikBuilder::on_request) hooks.
+#[derive(Debug)]
+pub struct RequestParts {
+  pub(crate) url: String,
+  pub(crate) headers: HeaderMap,
+}
+
+impl RequestParts {
+  /// The URL, which never contains the API key.
+  pub fn url(&self) -> &str {
+    &self.url
+  }
+
+  /// The headers, including the API key marked as sensitive.
+  pub fn headers(&self) -> &HeaderMap {
+    &self.headers
+  }
+
+  /// The headers, e.g. for adding a correlation ID.
+  pub fn headers_mut(&mut self) -> &mut HeaderMap {
+    &mut self.headers
+  }
+}
+
+/// What became of a request, as seen by
+/// [on_response](crate::WordnikBuilder::on_response) hooks.
+#[derive(Debug)]
+pub struct ResponseMeta<'a> {
+  url: &'a str,
+  elapsed: Duration,
+  status: Option<StatusCode>,
+  error: Option<&'a WordnikError>,
+}
+
+impl<'a> ResponseMeta<'a> {
+  pub(crate) fn new(
+    url: &'a str,
+    elapsed: Duration,
+    res: &'a Result<Fetched, WordnikError>,
+  ) -> ResponseMeta<'a> {
+    let status = match res {
+      Ok(Fetched::NotModified { .. }) => Some(StatusCode::NOT_MODIFIED),
+      Ok(_) => Some(StatusCode::OK),
+      Err(WordnikError::NotFound) => Some(StatusCode::NOT_FOUND),
+      Err(WordnikError::TooManyRequests(_)) => {
+        Some(StatusCode::TOO_MANY_REQUESTS)
+      }
+      Err(WordnikError::Http(e)) => e.status(),
+      Err(_) => None,
+    };
+    ResponseMeta {
+      url,
+      elapsed,
+      status,
+      error: res.as_ref().err(),
+    }
+  }
+
+  pub fn url(&self) -> &str {
+    self.url
+  }
+
+  /// The time from sending the request to having read the response.
+  pub fn elapsed(&self) -> Duration {
+    self.elapsed
+  }
+
+  /// The status of the response, or `None` if there was none, e.g.
+  /// because the connection failed. Successful responses other than
+  /// `304 Not Modified` are reported as `200 OK`, since a
+  /// [Requester](crate::Requester) only hands back the body.
+  pub fn status(&self) -> Option<StatusCode> {
+    self.status
+  }
+
+  /// The error the request failed with. It is returned to the caller
+  /// after the hooks have run.
+  pub fn error(&self) -> Option<&WordnikError> {
+    self.error
+  }
+}
+
+type RequestHook = Arc<dyn Fn(&mut RequestParts) + Send + Sync>;
+type ResponseHook = Arc<dyn Fn(&ResponseMeta) + Send + Sync>;
+
+#[derive(Clone, Default)]
+pub(crate) struct Hooks {
+  pub(crate) on_request: Vec<RequestHook>,
+  pub(crate) on_response: Vec<ResponseHook>,
+}
+
+impl Hooks {
+  pub(crate) fn before(&self, parts: &mut RequestParts) {
+    for hook in &self.on_request {
+      hook(parts);
+    }
+  }
+
+  pub(crate) fn after(&self, meta: &ResponseMeta) {
+    for hook in &self.on_response {
+      hook(meta);
+    }
+  }
+}
@@ -1,11 +1,108 @@
+//! A client for the [Wordnik](https://developer.wordnik.com/) word API.
+//!
+//! # Features
+//!
+//! * `native-tls` (default): TLS through the platform's library, i.e.
+//!   OpenSSL on Linux.
+//! * `rustls`: TLS through rustls, for systems without OpenSSL. Disable the
+//!   default features to drop native-tls:
+//!   `wordnik = { version = "0.2", default-features = false, features = ["blocking", "rustls"] }`.
+//!   If both are enabled, native-tls is used.
+//! * `blocking` (default): [Wordnik], the blocking client built on
+//!   `reqwest::blocking`. Disable the default features to build an
+//!   async-only client without it.
+//! * `async`: `AsyncWordnik`, an async client built on `reqwest::Client`.
+//! * `tracing`: a `tracing` span per API call, with its operation and word,
+//!   and debug events when the request is sent, its response arrives and
+//!   its body is read. The API key is never recorded.
+//! * `fixtures`: `Wordnik::with_fixture_dir`, recording responses to disk
+//!   and replaying them in tests.
+//! * `chrono`: [Timestamp] is a `chrono::DateTime<Utc>` instead of the
+//!   string the API sent.
+//!
+//! One of the TLS features is required, since the API is only served over
+//! HTTPS.
+
+#![cfg_attr(feature = "blocking", deny(rustdoc::all))]
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+compile_error!(
+  "either the `native-tls` or the `rustls` feature must be enabled"
+);
+
 use serde::Deserialize;
 use serde::Serialize;
+#[cfg(feature = "blocking")]
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fmt::Display;
+#[cfg(feature = "blocking")]
+use std::sync::Arc;
+#[cfg(feature = "blocking")]
+use std::time::Instant;
+
+#[cfg(feature = "async")]
+mod async_client;
+mod batch;
+mod builder;
+mod cache;
+mod call;
+mod de;
+mod error;
+mod etymology;
+mod export;
+#[cfg(feature = "fixtures")]
+mod fixtures;
+mod hooks;
+mod pages;
+mod pretty;
+mod ratelimit;
+mod report;
+mod requester;
+mod retry;
+mod scrabble;
+mod trace;
+
+#[cfg(feature = "async")]
+pub use async_client::AsyncWordnik;
+pub use batch::DEFAULT_CONCURRENCY;
+pub use builder::{
+  WordnikBuilder, DEFAULT_API_URL, DEFAULT_MAX_RESPONSE_SIZE,
+  DEFAULT_USER_AGENT,
+};
+pub use error::WordnikError;
+pub use etymology::{Etymology, EtymologySegment};
+pub use export::{
+  anki_note, to_csv, to_delimited, to_jsonl, to_tsv, write_jsonl,
+};
+#[cfg(feature = "fixtures")]
+pub use fixtures::FixtureMode;
+pub use hooks::{RequestParts, ResponseMeta};
+pub use pretty::{decode_entities, DefinitionOptions, GroupBy, PrettyOptions};
+pub use ratelimit::RateLimitStatus;
+pub use report::WordReport;
+pub use requester::{Fetched, Requester};
+pub use scrabble::scrabble_breakdown;
+
+#[cfg(feature = "blocking")]
+use cache::Caches;
+#[cfg(feature = "blocking")]
+use call::Call;
+use call::Config;
+#[cfg(feature = "blocking")]
+use hooks::Hooks;
+#[cfg(feature = "blocking")]
+use pages::ExamplesIter;
+#[cfg(feature = "blocking")]
+use pretty::{format_definitions, format_definitions_markdown, Report};
+#[cfg(feature = "blocking")]
+use ratelimit::RateLimits;
 
 /// The struct representing the API endpoint.
 ///
+/// The API key is never shown in full, neither in `Debug` output nor in
+/// the URLs of error messages.
+///
 /// # Example
 /// ```
 /// use wordnik::Wordnik;
@@ -15,14 +112,95 @@ use std::fmt::Display;
 ///   "https://api.wordnik.com/v4/word.json/".to_string(),
 /// );
 /// ```
+///
+/// ```
+/// use wordnik::Wordnik;
+///
+/// let api = Wordnik::new(
+///   "SECRET_API_KEY".to_string(),
+///   "http://127.0.0.1:9/v4/word.json/".to_string(),
+/// );
+/// assert!(!format!("{:?}", api).contains("SECRET_API_KEY"));
+///
+/// let err = api.get_definitions("word").unwrap_err();
+/// assert!(!err.to_string().contains("SECRET_API_KEY"));
+/// assert!(!format!("{:?}", err).contains("SECRET_API_KEY"));
+/// ```
+///
+/// `Wordnik` is `Clone + Send + Sync`. Clones share the requester, the
+/// caches and the rate limits, so it can be cloned into worker threads or
+/// shared in an [Arc].
+///
+/// ```
+/// # use reqwest::header::HeaderMap;
+/// # use serde_json::json;
+/// use std::sync::Arc;
+/// use wordnik::Wordnik;
+///
+/// fn assert_send_sync<T: Clone + Send + Sync>() {}
+/// assert_send_sync::<Wordnik>();
+///
+/// let api = Arc::new(Wordnik::with_requester(
+///   "YOUR_API_KEY".to_string(),
+///   "https://api.wordnik.com/v4/word.json/".to_string(),
+///   |_: &str, _: &HeaderMap| Ok(json!({ "value": 5 })),
+/// ));
+/// let workers: Vec<_> = (0..2)
+///   .map(|_| {
+///     let api = Arc::clone(&api);
+///     std::thread::spawn(move || api.get_scrabble_score("word").unwrap().value)
+///   })
+///   .collect();
+/// for worker in workers {
+///   assert_eq!(worker.join().unwrap(), 5);
+/// }
+/// ```
+#[cfg(feature = "blocking")]
+#[derive(Clone)]
 pub struct Wordnik {
-  api_key: String,
-  entry: String,
+  config: Config,
+  requester: Arc<dyn Requester>,
+  hooks: Hooks,
+  cache: Caches,
+  bypass_cache: bool,
+  rate_limits: RateLimits,
+}
+
+#[cfg(feature = "blocking")]
+impl std::fmt::Debug for Wordnik {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Wordnik")
+      .field("api_key", &error::redact_key(&self.config.api_key))
+      .field("entry", &self.config.entry)
+      .finish_non_exhaustive()
+  }
 }
 
 /// The supported operations.
 ///
 /// See [Wordnik docs](https://developer.wordnik.com/docs#/word).
+///
+/// Operations parse from their API spelling, e.g. "relatedWords", or
+/// from it in any case with dashes or underscores between the words, e.g.
+/// "related-words".
+///
+/// # Example
+///
+/// ```
+/// use std::str::FromStr;
+/// use wordnik::Operation;
+///
+/// for op in Operation::all() {
+///   assert_eq!(Operation::from_str(&op.to_string()).as_ref(), Ok(op));
+/// }
+/// assert_eq!("related-words".parse(), Ok(Operation::RelatedWords));
+/// assert_eq!(Operation::try_from("SCRABBLE_SCORE"), Ok(Operation::ScrabbleScore));
+///
+/// let err = "define".parse::<Operation>().unwrap_err();
+/// assert!(err.to_string().contains("relatedWords"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum Operation {
   Audio,
   Definitions,
@@ -35,8 +213,140 @@ pub enum Operation {
   RelatedWords,
   ScrabbleScore,
   TopExample,
+  /// An endpoint without a variant of its own, e.g.
+  /// "definitions/{id}/relatedWords", appended to the word as is. Its
+  /// segments are percent-encoded, so they can't add a query, and
+  /// requests with empty, `.` or `..` segments fail with
+  /// [InvalidOperation](WordnikError::InvalidOperation). Use
+  /// [Operation::custom] to check it up front.
+  Custom(String),
+}
+
+impl Operation {
+  /// Every operation, in the order they are declared, except
+  /// [Custom](Operation::Custom).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::collections::HashMap;
+  /// use wordnik::Operation;
+  ///
+  /// let counts: HashMap<Operation, usize> =
+  ///   Operation::ALL.iter().map(|op| (op.clone(), 0)).collect();
+  /// assert_eq!(counts.len(), Operation::ALL.len());
+  /// ```
+  pub const ALL: [Operation; 11] = [
+    Operation::Audio,
+    Operation::Definitions,
+    Operation::Etymologies,
+    Operation::Examples,
+    Operation::Frequency,
+    Operation::Hyphenation,
+    Operation::Phrases,
+    Operation::Pronunciations,
+    Operation::RelatedWords,
+    Operation::ScrabbleScore,
+    Operation::TopExample,
+  ];
+
+  /// Every operation, in the order they are declared, e.g. for help text.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wordnik::Operation;
+  ///
+  /// let names: Vec<String> = Operation::all().iter().map(|o| o.to_string()).collect();
+  /// assert_eq!(names.len(), 11);
+  /// assert_eq!(names[0], "audio");
+  /// assert!(names.contains(&"relatedWords".to_string()));
+  /// ```
+  pub fn all() -> &'static [Operation] {
+    &Operation::ALL
+  }
+
+  /// A [Custom](Operation::Custom) operation, checking that `path` can be
+  /// used in a URL.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{Operation, Wordnik};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert_eq!(
+  ///       url,
+  ///       "https://api.wordnik.com/v4/word.json/word/definitions/W5%3Fx=1/relatedWords",
+  ///     );
+  ///     Ok(json!([]))
+  ///   },
+  /// );
+  /// let op = Operation::custom("definitions/W5?x=1/relatedWords").unwrap();
+  /// assert_eq!(op.to_string(), "definitions/W5?x=1/relatedWords");
+  /// api.get_raw("word", op).unwrap();
+  ///
+  /// assert!(Operation::custom("../wordList").is_err());
+  /// assert!(Operation::custom("definitions/").is_err());
+  /// ```
+  pub fn custom(path: &str) -> Result<Operation, WordnikError> {
+    let op = Operation::Custom(path.to_string());
+    call::encode_operation(&op)?;
+    Ok(op)
+  }
 }
 
+impl std::str::FromStr for Operation {
+  type Err = ParseOperationError;
+
+  fn from_str(s: &str) -> Result<Operation, ParseOperationError> {
+    let key = |s: &str| -> String {
+      s.chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .collect::<String>()
+        .to_lowercase()
+    };
+    let wanted = key(s.trim());
+    Operation::all()
+      .iter()
+      .find(|op| key(&op.to_string()) == wanted)
+      .cloned()
+      .ok_or_else(|| ParseOperationError(s.to_string()))
+  }
+}
+
+impl TryFrom<&str> for Operation {
+  type Error = ParseOperationError;
+
+  fn try_from(s: &str) -> Result<Operation, ParseOperationError> {
+    s.parse()
+  }
+}
+
+/// The error of parsing an unknown [Operation], holding the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOperationError(pub String);
+
+impl Display for ParseOperationError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let valid: Vec<String> =
+      Operation::all().iter().map(Operation::to_string).collect();
+    write!(
+      f,
+      "unknown operation {:?}, expected one of: {}",
+      self.0,
+      valid.join(", ")
+    )
+  }
+}
+
+impl std::error::Error for ParseOperationError {}
+
 impl Display for Operation {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     match &self {
@@ -51,156 +361,3870 @@ impl Display for Operation {
       Operation::RelatedWords => write!(f, "relatedWords"),
       Operation::ScrabbleScore => write!(f, "scrabbleScore"),
       Operation::TopExample => write!(f, "topExample"),
+      Operation::Custom(path) => write!(f, "{}", path),
     }
   }
 }
 
+/// The struct representing API response for the word itself.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WordObject {
+  #[serde(default)]
+  pub id: i64,
+  pub word: String,
+  /// The base form of an inflected word, e.g. "run" for "running".
+  #[serde(default)]
+  pub canonical_form: Option<String>,
+  /// Spellings the API suggests instead, e.g. for a misspelt word.
+  #[serde(default)]
+  pub suggestions: Vec<String>,
+}
+
 /// The struct representing API response for word definition.
+///
+/// Surrounding whitespace is trimmed from the definition, and runs of
+/// whitespace inside it are collapsed to a single space.
+///
+/// # Example
+///
+/// ```
+/// use wordnik::Definition;
+///
+/// let def: Definition = serde_json::from_str(
+///   r#"{
+///     "word": "word",
+///     "text": "  A unit of\n  language.  ",
+///     "attributionText": "from The American Heritage Dictionary",
+///     "sourceDictionary": "ahd-5",
+///     "attributionUrl": "https://ahdictionary.com/",
+///     "wordnikUrl": "https://www.wordnik.com/words/word"
+///   }"#,
+/// )
+/// .unwrap();
+/// assert_eq!(def.definition.as_deref(), Some("A unit of language."));
+/// assert_eq!(def.part_of_speech, None);
+/// assert!(def.labels.is_empty());
+/// assert!(def.example_uses.is_empty());
+///
+/// let def: Definition = serde_json::from_str(
+///   r#"{
+///     "word": "word",
+///     "text": "A unit of language.",
+///     "partOfSpeech": "",
+///     "attributionText": "from The American Heritage Dictionary",
+///     "sourceDictionary": "ahd-5",
+///     "attributionUrl": "https://ahdictionary.com/",
+///     "wordnikUrl": "https://www.wordnik.com/words/word"
+///   }"#,
+/// )
+/// .unwrap();
+/// assert_eq!(def.part_of_speech.as_deref(), Some(""));
+/// ```
+///
+/// A full response from The American Heritage Dictionary:
+///
+/// ```
+/// use wordnik::{Definition, RelationshipType};
+///
+/// let def: Definition = serde_json::from_str(
+///   r#"{
+///     "id": "T5105100-3",
+///     "partOfSpeech": "adjective",
+///     "attributionText": "from The American Heritage® Dictionary of the English Language, 5th Edition.",
+///     "sourceDictionary": "ahd-5",
+///     "text": "Ill-mannered or rude.",
+///     "sequence": "3",
+///     "score": 0,
+///     "labels": [{ "text": "Archaic", "type": "register" }],
+///     "citations": [
+///       { "source": "Shakespeare", "cite": "Thou art a saucy fellow." }
+///     ],
+///     "word": "saucy",
+///     "relatedWords": [
+///       { "relationshipType": "synonym", "words": ["impudent", "pert"] }
+///     ],
+///     "exampleUses": [{ "text": "a saucy remark" }],
+///     "textProns": [],
+///     "notes": [],
+///     "attributionUrl": "https://ahdictionary.com/",
+///     "wordnikUrl": "https://www.wordnik.com/words/saucy"
+///   }"#,
+/// )
+/// .unwrap();
+/// assert_eq!(def.id, "T5105100-3");
+/// assert_eq!(def.sequence, 3);
+/// assert_eq!(def.labels[0].text, "Archaic");
+/// assert_eq!(def.labels[0].label_type, "register");
+/// assert_eq!(def.example_uses[0].text, "a saucy remark");
+/// assert_eq!(def.citations[0].source, "Shakespeare");
+/// assert_eq!(def.related_words[0].relationship_type, RelationshipType::Synonym);
+///
+/// let archaic = def.labels.iter().any(|l| l.text.eq_ignore_ascii_case("archaic"));
+/// assert!(archaic);
+/// ```
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Definition {
   pub word: String,
-  #[serde(default, rename = "text")]
-  pub definition: String,
+  /// The text, or `None` if the response had none.
+  #[serde(default, rename = "text", deserialize_with = "de::collapsed_text")]
+  pub definition: Option<String>,
+  /// The part of speech, or `None` if the response had none. It may also
+  /// be present but empty.
   #[serde(default)]
-  pub part_of_speech: String,
+  pub part_of_speech: Option<String>,
   pub attribution_text: String,
   pub source_dictionary: String,
   pub attribution_url: String,
   pub wordnik_url: String,
+  #[serde(default, deserialize_with = "de::nullable")]
+  pub id: String,
+  /// The position of the sense within its dictionary's entry.
+  #[serde(default, deserialize_with = "de::lenient_number")]
+  pub sequence: i64,
+  #[serde(default, deserialize_with = "de::lenient_number")]
+  pub score: f64,
+  /// Usage labels such as "slang" or "archaic".
+  #[serde(default, deserialize_with = "de::nullable")]
+  pub labels: Vec<Label>,
+  /// Sentences showing this sense in use, unlike the examples of
+  /// [get_examples](Wordnik::get_examples), which are for the word.
+  #[serde(default, deserialize_with = "de::nullable")]
+  pub example_uses: Vec<ExampleUse>,
+  #[serde(default, deserialize_with = "de::nullable")]
+  pub citations: Vec<Citation>,
+  #[serde(default, deserialize_with = "de::nullable")]
+  pub related_words: Vec<RelatedWords>,
+  /// The other dictionaries with the same text, filled in by
+  /// [dedup_definitions] when it drops their definitions.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub duplicate_sources: Vec<String>,
 }
 
-impl Definition {
-  fn to_pretty(&self) -> String {
-    if self.part_of_speech.clone() == "" {
-      self.definition.clone() + "\n"
-    } else {
-      self.part_of_speech.clone() + " " + &self.definition + "\n"
-    }
+impl Display for Definition {
+  /// The part of speech and the text, with the attribution on a second
+  /// line.
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let opts = DefinitionOptions::new().attribution(true);
+    write!(f, "{}", self.pretty(&opts))
   }
 }
 
-impl Wordnik {
-  pub fn new(api_key: String, entry: String) -> Wordnik {
-    Wordnik { api_key, entry }
+/// A usage label of a [Definition], e.g. "archaic" of type "register".
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Label {
+  #[serde(default, deserialize_with = "de::nullable")]
+  pub text: String,
+  #[serde(default, rename = "type", deserialize_with = "de::nullable")]
+  pub label_type: String,
+}
+
+/// A sentence showing a [Definition] in use.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExampleUse {
+  #[serde(default, deserialize_with = "de::nullable")]
+  pub text: String,
+}
+
+impl ExampleUse {
+  /// The text with HTML entities decoded.
+  pub fn plain_text(&self) -> String {
+    decode_entities(&self.text)
   }
+}
 
-  fn make_request(
-    &self,
-    url: String,
-  ) -> Result<Value, Box<dyn std::error::Error>> {
-    let client = reqwest::blocking::Client::new();
-    let res = client
-      .get(url)
-      .header("Content-Type", "application/json")
-      .send()?
-      .text()?;
+/// A quotation supporting a [Definition], and where it is from.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Citation {
+  #[serde(default, deserialize_with = "de::nullable")]
+  pub source: String,
+  #[serde(default, deserialize_with = "de::nullable")]
+  pub cite: String,
+}
 
-    let res: Value = serde_json::from_str(&res)?;
-    Ok(res)
+impl Citation {
+  /// The quotation with HTML entities decoded.
+  pub fn plain_cite(&self) -> String {
+    decode_entities(&self.cite)
   }
+}
 
-  /// Get definitions of a word.
-  ///
-  /// For a pretty string of definitions,
-  /// see [get_definitions_pretty](Self::get_definitions_pretty).
+impl Definition {
+  /// Parse [attribution_url](Self::attribution_url).
   ///
   /// # Example
   ///
   /// ```
-  /// use wordnik::Wordnik;
-  ///
-  /// let api = Wordnik::new(
-  ///   "YOUR_API_KEY".to_string(),
-  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
-  /// );
+  /// use wordnik::Definition;
   ///
-  /// let v = api.get_definitions("word").unwrap();
-  /// println!("{:#?}", v);
+  /// let def = Definition {
+  ///   attribution_url: "https://ahdictionary.com/".to_string(),
+  ///   wordnik_url: "not a url".to_string(),
+  ///   ..Definition::default()
+  /// };
+  /// assert_eq!(def.attribution_url_parsed().unwrap().host_str(), Some("ahdictionary.com"));
+  /// assert!(def.wordnik_url_parsed().is_err());
   /// ```
-  pub fn get_definitions(
-    &self,
-    word: &str,
-  ) -> Result<Vec<Definition>, Box<dyn std::error::Error>> {
-    let url = self.entry.clone()
-      + word
-      + "/"
-      + Operation::Definitions.to_string().as_str()
-      + "?api_key="
-      + &self.api_key;
-    let res = self.make_request(url)?;
-    let mut definitions: Vec<Definition> = serde_json::from_value(res)?;
-    definitions.retain(|def| def.definition != "");
-    Ok(definitions)
+  pub fn attribution_url_parsed(&self) -> Result<url::Url, url::ParseError> {
+    url::Url::parse(&self.attribution_url)
   }
 
-  /// Get definitions of a word.
+  /// Parse [wordnik_url](Self::wordnik_url).
+  pub fn wordnik_url_parsed(&self) -> Result<url::Url, url::ParseError> {
+    url::Url::parse(&self.wordnik_url)
+  }
+
+  /// The text without the markup tags some dictionaries use, such as
+  /// `<xref>` and `<em>`, keeping the text inside them. Unbalanced markup
+  /// is left alone where it isn't a tag. HTML entities such as `&amp;`
+  /// and `&#233;` are decoded; unknown ones are kept.
   ///
-  /// # Arguments
+  /// # Example
   ///
-  /// * `word`      - The word whose meaning you want.
-  /// * `textwidth` - The column at which to wrap the text.
-  ///                 Pass 0 if you want want to wrap at all.
-  /// * `dicts`     - The dictionaries you want to use.
-  ///                 Pass an empty vector if you want to use all.
-  ///                 The available dictionaries are:
-  ///                   - "ahd-5": American Heritage Dictionary
-  ///                   - "century": The Century Dictionary
-  ///                   - "gcide": GNU version of the Collaborative International Dictionary of English
-  ///                   - "wiktionary": Wiktionary
-  ///                   - "wordnet": WordNet 3.0 Copyright 2006 by Princeton University
+  /// ```
+  /// use wordnik::Definition;
   ///
-  /// # Examples
+  /// let cases = [
+  ///   ("See <xref>run</xref>.", "See run."),
+  ///   (
+  ///     "<em>Figuratively</em>, <internalXref urlencoded=\"pace\">a pace</internalXref>.",
+  ///     "Figuratively, a pace.",
+  ///   ),
+  ///   ("<strong><em>Nested</em> tags</strong>", "Nested tags"),
+  ///   ("A <br/>self-closing <spn />tag", "A self-closing tag"),
+  ///   ("Unclosed <xref>tag", "Unclosed tag"),
+  ///   ("Stray </em> closing", "Stray closing"),
+  ///   ("x < y and y > z", "x < y and y > z"),
+  ///   ("Dangling <xref", "Dangling <xref"),
+  ///   ("", ""),
+  ///   ("Salt &amp; pepper", "Salt & pepper"),
+  ///   ("&quot;Caf&eacute;&quot; or caf&#233; or caf&#xE9;", "\"Café\" or café or café"),
+  ///   ("&amp;amp; is decoded once", "&amp; is decoded once"),
+  ///   ("&lt;em&gt; is text, not a tag", "<em> is text, not a tag"),
+  ///   ("&bogus; &#xZZ; &#1114112; & alone", "&bogus; &#xZZ; &#1114112; & alone"),
+  /// ];
+  /// for (text, plain) in cases {
+  ///   let def = Definition {
+  ///     definition: Some(text.to_string()),
+  ///     ..Definition::default()
+  ///   };
+  ///   assert_eq!(def.plain_text(), plain, "for {:?}", text);
+  /// }
+  /// assert_eq!(Definition::default().plain_text(), "");
+  /// ```
+  pub fn plain_text(&self) -> String {
+    let text = self.definition.as_deref().unwrap_or_default();
+    decode_entities(&pretty::strip_tags(text))
+  }
+
+  /// Format the definition for humans, as in the pretty printers.
+  ///
+  /// # Example
   ///
   /// ```
-  /// use wordnik::Wordnik;
+  /// use wordnik::{Definition, DefinitionOptions};
   ///
-  /// let api = Wordnik::new(
-  ///   "YOUR_API_KEY".to_string(),
-  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// let def = Definition {
+  ///   definition: Some("A unit of <em>language</em>.".to_string()),
+  ///   part_of_speech: Some("noun".to_string()),
+  ///   attribution_text: "from The American Heritage Dictionary".to_string(),
+  ///   ..Definition::default()
+  /// };
+  /// assert_eq!(def.pretty(&DefinitionOptions::new()), "noun A unit of language.");
+  /// assert_eq!(
+  ///   def.pretty(&DefinitionOptions::new().part_of_speech(false)),
+  ///   "A unit of language.",
+  /// );
+  /// // Display adds the attribution.
+  /// assert_eq!(
+  ///   def.to_string(),
+  ///   "noun A unit of language.\nfrom The American Heritage Dictionary",
   /// );
-  ///
-  /// let v = api.get_definitions_pretty("word", vec!["ahd-5", "wordnet"]).unwrap();
-  /// println!("{}", v);
   /// ```
-  pub fn get_definitions_pretty(
-    &self,
-    word: &str,
-    textwidth: usize,
-    dicts: Vec<&str>,
-  ) -> Result<String, Box<dyn std::error::Error>> {
-    let definitions = self.get_definitions(word)?;
-    let mut hm_definitions: HashMap<String, Vec<Definition>> = HashMap::new();
-    let filter_dict = dicts.len() > 0;
-    for i in &definitions {
-      if hm_definitions.contains_key(&i.attribution_text) {
-        let v = hm_definitions.get_mut(&i.attribution_text).unwrap();
-        v.push(i.clone());
-      } else if !filter_dict || dicts.contains(&i.source_dictionary.as_str()) {
-        let mut v: Vec<Definition> = Vec::new();
-        v.push(i.clone());
-        hm_definitions.insert(i.attribution_text.clone(), v);
+  pub fn pretty(&self, opts: &DefinitionOptions) -> String {
+    pretty::format_definition(self, opts)
+  }
+}
+
+/// Drop definitions whose text repeats an earlier one, keeping the first
+/// and so its attribution. The dictionaries of the dropped ones are added
+/// to its [duplicate_sources](Definition::duplicate_sources).
+///
+/// Texts are compared without markup, case, runs of whitespace or trailing
+/// punctuation, since dictionaries often copy definitions from each other
+/// and only differ in such details.
+///
+/// # Example
+/// ```
+/// use wordnik::{dedup_definitions, Definition};
+///
+/// let def = |text: &str, source: &str| Definition {
+///   definition: Some(text.to_string()),
+///   source_dictionary: source.to_string(),
+///   ..Definition::default()
+/// };
+/// let mut defs = vec![
+///   def("A unit of language.", "ahd-5"),
+///   def("Speech or talk.", "ahd-5"),
+///   def(" a unit of language. ", "gcide"),
+/// ];
+/// dedup_definitions(&mut defs);
+/// assert_eq!(defs.len(), 2);
+/// assert_eq!(defs[0].source_dictionary, "ahd-5");
+/// assert_eq!(defs[0].duplicate_sources, ["gcide"]);
+///
+/// // Near-duplicates collapse too.
+/// let mut defs = vec![
+///   def("To move <xref>swiftly</xref>.", "century"),
+///   def("to  move swiftly", "gcide"),
+///   def("To move swiftly;", "wiktionary"),
+///   def("To move swiftly on foot.", "ahd-5"),
+/// ];
+/// dedup_definitions(&mut defs);
+/// assert_eq!(defs.len(), 2);
+/// assert_eq!(defs[0].duplicate_sources, ["gcide", "wiktionary"]);
+/// assert!(defs[1].duplicate_sources.is_empty());
+/// ```
+pub fn dedup_definitions(definitions: &mut Vec<Definition>) {
+  let mut first: HashMap<String, usize> = HashMap::new();
+  let mut kept: Vec<Definition> = Vec::with_capacity(definitions.len());
+  for def in definitions.drain(..) {
+    let key = dedup_key(&def);
+    match first.get(&key) {
+      Some(&i) => {
+        let source = def.source_dictionary;
+        let kept = &mut kept[i];
+        if !source.is_empty()
+          && source != kept.source_dictionary
+          && !kept.duplicate_sources.contains(&source)
+        {
+          kept.duplicate_sources.push(source);
+        }
+      }
+      None => {
+        first.insert(key, kept.len());
+        kept.push(def);
       }
     }
+  }
+  *definitions = kept;
+}
 
-    let mut s = "".to_string();
-    for (attribution_text, definitions) in hm_definitions.iter() {
-      s = s
-        + attribution_text
-        + "\n"
-        + definitions
-          .iter()
-          .fold("".into(), |acc, d| {
-            [acc, "  * ".into(), d.to_pretty()].join("")
-          })
-          .as_str()
-        + "\n";
-    }
+/// The text of `def` as compared by [dedup_definitions].
+fn dedup_key(def: &Definition) -> String {
+  let text = def.plain_text().to_lowercase();
+  let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+  text
+    .trim_end_matches(['.', ',', ';', ':', '!', '?'])
+    .trim_end()
+    .to_string()
+}
 
-    if textwidth != 0 {
-      s = textwrap::fill(s.as_str(), textwidth);
+/// Group `definitions` by [source dictionary](Definition::source_dictionary),
+/// in the order the dictionaries first appear, keeping the API's order,
+/// which ranks by relevance, within each. This is the grouping of
+/// [get_definitions_pretty](Wordnik::get_definitions_pretty).
+///
+/// # Example
+/// ```
+/// use wordnik::{group_by_source, Definition};
+///
+/// let def = |text: &str, source: &str| Definition {
+///   definition: Some(text.to_string()),
+///   source_dictionary: source.to_string(),
+///   ..Definition::default()
+/// };
+/// let groups = group_by_source(vec![
+///   def("A unit of language.", "ahd-5"),
+///   def("A promise.", "wiktionary"),
+///   def("Speech or talk.", "ahd-5"),
+/// ]);
+/// let sources: Vec<&str> = groups.iter().map(|(s, _)| s.as_str()).collect();
+/// assert_eq!(sources, ["ahd-5", "wiktionary"]);
+/// assert_eq!(groups[0].1[1].definition.as_deref(), Some("Speech or talk."));
+/// ```
+pub fn group_by_source(
+  definitions: Vec<Definition>,
+) -> Vec<(String, Vec<Definition>)> {
+  let mut groups: Vec<(String, Vec<Definition>)> = Vec::new();
+  for def in definitions {
+    match groups.iter_mut().find(|(s, _)| *s == def.source_dictionary) {
+      Some((_, group)) => group.push(def),
+      None => groups.push((def.source_dictionary.clone(), vec![def])),
     }
+  }
+  groups
+}
+
+/// How [sort_definitions] orders definitions.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SortOrder {
+  /// The order the API returned them in, which ranks by relevance.
+  #[default]
+  Api,
+  /// The given parts of speech first, in that order, then the rest.
+  /// "verb" also matches "verb-transitive" and "verb-intransitive", and
+  /// case is ignored.
+  PartOfSpeech(Vec<String>),
+  /// The given [source dictionaries](Definition::source_dictionary)
+  /// first, in that order, then the rest.
+  SourceDictionary(Vec<String>),
+}
+
+/// Order `definitions` by `order`. The sort is stable, so definitions
+/// that rank the same stay in the order the API returned them.
+///
+/// # Example
+/// ```
+/// use wordnik::{sort_definitions, Definition, SortOrder};
+///
+/// let def = |text: &str, part_of_speech: &str| Definition {
+///   definition: Some(text.to_string()),
+///   part_of_speech: Some(part_of_speech.to_string()),
+///   ..Definition::default()
+/// };
+/// let mut defs = vec![
+///   def("To move swiftly.", "verb-intransitive"),
+///   def("Flowing.", "adjective"),
+///   def("A pace faster than a walk.", "noun"),
+///   def("To manage.", "verb-transitive"),
+/// ];
+/// let order = SortOrder::PartOfSpeech(vec!["noun".into(), "verb".into()]);
+/// sort_definitions(&mut defs, &order);
+/// let texts: Vec<_> = defs.iter().filter_map(|d| d.definition.as_deref()).collect();
+/// assert_eq!(
+///   texts,
+///   ["A pace faster than a walk.", "To move swiftly.", "To manage.", "Flowing."],
+/// );
+/// ```
+pub fn sort_definitions(definitions: &mut [Definition], order: &SortOrder) {
+  match order {
+    SortOrder::Api => {}
+    SortOrder::PartOfSpeech(priorities) => definitions.sort_by_key(|def| {
+      let part_of_speech = def.part_of_speech.as_deref().unwrap_or_default();
+      let part_of_speech = part_of_speech.to_lowercase();
+      let base = part_of_speech.split('-').next().unwrap_or_default();
+      priority(priorities, |p| {
+        let p = p.to_lowercase();
+        p == part_of_speech || p == base
+      })
+    }),
+    SortOrder::SourceDictionary(priorities) => definitions
+      .sort_by_key(|def| priority(priorities, |p| *p == def.source_dictionary)),
+  }
+}
+
+/// The position of the first of `priorities` that `matches`, or one past
+/// the last.
+fn priority(priorities: &[String], matches: impl Fn(&String) -> bool) -> usize {
+  priorities
+    .iter()
+    .position(matches)
+    .unwrap_or(priorities.len())
+}
+
+/// The struct representing API response for word pronunciation.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pronunciation {
+  #[serde(default)]
+  pub seq: i64,
+  pub raw: String,
+  pub raw_type: String,
+  #[serde(default)]
+  pub attribution_text: String,
+  #[serde(default)]
+  pub attribution_url: String,
+}
+
+/// When something was created, e.g. [Audio::created_at].
+///
+/// With the `chrono` feature, timestamps are parsed into a
+/// `chrono::DateTime<Utc>`, and ones that don't parse are dropped.
+/// Otherwise they are kept as the string the API sent, such as
+/// `"2009-03-15T15:30:37.000+0000"`.
+#[cfg(feature = "chrono")]
+pub type Timestamp = chrono::DateTime<chrono::Utc>;
+/// When something was created, e.g. [Audio::created_at].
+///
+/// With the `chrono` feature, timestamps are parsed into a
+/// `chrono::DateTime<Utc>`, and ones that don't parse are dropped.
+/// Otherwise they are kept as the string the API sent, such as
+/// `"2009-03-15T15:30:37.000+0000"`.
+#[cfg(not(feature = "chrono"))]
+pub type Timestamp = String;
+
+/// The struct representing API response for a recorded pronunciation.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Audio {
+  #[serde(default)]
+  pub id: i64,
+  pub word: String,
+  pub file_url: String,
+  #[serde(default)]
+  pub audio_type: String,
+  /// The length of the recording in seconds.
+  #[serde(default, deserialize_with = "de::lenient_number")]
+  pub duration: f64,
+  #[serde(default)]
+  pub created_by: String,
+  #[serde(default, deserialize_with = "de::timestamp")]
+  pub created_at: Option<Timestamp>,
+  #[serde(default)]
+  pub attribution_text: String,
+  #[serde(default)]
+  pub attribution_url: String,
+}
+
+impl Audio {
+  /// The file format, e.g. "mp3", sniffed from the extension in
+  /// [file_url](Self::file_url) and lowercased. The API also puts it in
+  /// an earlier segment, as in `.../audioFile.mp3/abc`, so the segments
+  /// are searched from the last. `None` if there is no extension.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wordnik::Audio;
+  ///
+  /// let audio = |file_url: &str| Audio {
+  ///   file_url: file_url.to_string(),
+  ///   ..Audio::default()
+  /// };
+  /// assert_eq!(audio("https://example.com/a/word.OGG?v=2").format().as_deref(), Some("ogg"));
+  /// assert_eq!(
+  ///   audio("https://api.wordnik.com/v4/audioFile.mp3/abc").format().as_deref(),
+  ///   Some("mp3"),
+  /// );
+  /// assert_eq!(audio("https://example.com/word").format(), None);
+  /// ```
+  pub fn format(&self) -> Option<String> {
+    let url = url::Url::parse(&self.file_url).ok()?;
+    url.path_segments()?.rev().find_map(|segment| {
+      let (_, extension) = segment.rsplit_once('.')?;
+      let valid = !extension.is_empty()
+        && extension.chars().all(|c| c.is_ascii_alphanumeric());
+      valid.then(|| extension.to_ascii_lowercase())
+    })
+  }
+}
+
+/// The recordings in `audio` of `format`, e.g. "mp3", ignoring case. See
+/// [Audio::format].
+pub fn audio_by_format<'a>(audio: &'a [Audio], format: &str) -> Vec<&'a Audio> {
+  audio
+    .iter()
+    .filter(|a| a.format().is_some_and(|f| f.eq_ignore_ascii_case(format)))
+    .collect()
+}
+
+/// The first recording in `audio` of the first of `formats` there is one
+/// of, e.g. an MP3 one if there is, else an Ogg one for `["mp3", "ogg"]`.
+///
+/// # Example
+///
+/// ```
+/// use wordnik::{audio_by_format, preferred_audio, Audio};
+///
+/// let audio = |id: i64, file_url: &str| Audio {
+///   id,
+///   file_url: file_url.to_string(),
+///   ..Audio::default()
+/// };
+/// let recordings = [
+///   audio(1, "https://example.com/1.ogg"),
+///   audio(2, "https://example.com/2.mp3"),
+///   audio(3, "https://example.com/3.mp3"),
+/// ];
+/// let best = preferred_audio(&recordings, &["mp3", "ogg"]).unwrap();
+/// assert_eq!(best.id, 2);
+/// assert_eq!(preferred_audio(&recordings, &["wav"]), None);
+/// assert_eq!(audio_by_format(&recordings, "MP3").len(), 2);
+/// ```
+pub fn preferred_audio<'a>(
+  audio: &'a [Audio],
+  formats: &[&str],
+) -> Option<&'a Audio> {
+  formats
+    .iter()
+    .find_map(|format| audio_by_format(audio, format).into_iter().next())
+}
+
+/// The struct representing API response for a word usage example.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Example {
+  #[serde(default)]
+  pub word: String,
+  pub text: String,
+  #[serde(default)]
+  pub title: String,
+  #[serde(default)]
+  pub url: String,
+  #[serde(default)]
+  pub year: Option<i32>,
+  #[serde(default)]
+  pub rating: f64,
+  #[serde(default)]
+  pub document_id: i64,
+  #[serde(default)]
+  pub example_id: i64,
+}
 
-    Ok(s)
+impl Example {
+  /// The text with HTML entities such as `&quot;` decoded, as in the
+  /// pretty printers. Unknown entities are kept.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wordnik::Example;
+  ///
+  /// let example = Example {
+  ///   text: "&quot;Fish &amp; chips,&quot; she said.".to_string(),
+  ///   ..Example::default()
+  /// };
+  /// assert_eq!(example.plain_text(), "\"Fish & chips,\" she said.");
+  /// ```
+  pub fn plain_text(&self) -> String {
+    decode_entities(&self.text)
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExampleSearchResults {
+  #[serde(default, deserialize_with = "de::nullable")]
+  examples: Vec<Example>,
+  #[serde(default, alias = "count")]
+  total_results: Option<usize>,
+}
+
+/// The Scrabble score of a word, as returned by
+/// [get_scrabble_score](Wordnik::get_scrabble_score).
+#[derive(
+  Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct ScrabbleScore {
+  /// The points of the word's tiles, without board bonuses.
+  pub value: i64,
+}
+
+/// One page of results from a list endpoint.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Paged<T> {
+  /// The results on this page.
+  pub items: Vec<T>,
+  /// The number of results across all pages. When the API doesn't report
+  /// it, this is the number of items on this page.
+  pub total: usize,
+}
+
+/// Optional parameters for [get_examples_with](Wordnik::get_examples_with).
+///
+/// # Example
+///
+/// ```
+/// use wordnik::ExamplesQuery;
+///
+/// let query = ExamplesQuery::new().min_year(2000).max_year(2020);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ExamplesQuery {
+  min_year: Option<i32>,
+  max_year: Option<i32>,
+  skip: Option<usize>,
+  limit: Option<usize>,
+  page_size: Option<usize>,
+}
+
+impl ExamplesQuery {
+  pub fn new() -> ExamplesQuery {
+    ExamplesQuery::default()
+  }
+
+  /// Only return examples from this year or later.
+  pub fn min_year(mut self, year: i32) -> ExamplesQuery {
+    self.min_year = Some(year);
+    self
+  }
+
+  /// Only return examples from this year or earlier.
+  pub fn max_year(mut self, year: i32) -> ExamplesQuery {
+    self.max_year = Some(year);
+    self
+  }
+
+  /// Skip this many examples, for paging through the results.
+  pub fn skip(mut self, skip: usize) -> ExamplesQuery {
+    self.skip = Some(skip);
+    self
+  }
+
+  /// Return at most this many examples. When iterating with
+  /// [examples_iter_with](Wordnik::examples_iter_with), this bounds the
+  /// examples fetched over all pages.
+  pub fn limit(mut self, limit: usize) -> ExamplesQuery {
+    self.limit = Some(limit);
+    self
+  }
+
+  /// When iterating with
+  /// [examples_iter_with](Wordnik::examples_iter_with), fetch this many
+  /// examples per request instead of 50. Not used otherwise.
+  pub fn page_size(mut self, page_size: usize) -> ExamplesQuery {
+    self.page_size = Some(page_size);
+    self
+  }
+
+  fn params(&self) -> Vec<(&'static str, String)> {
+    let mut params = Vec::new();
+    if let Some(year) = self.min_year {
+      params.push(("minYear", year.to_string()));
+    }
+    if let Some(year) = self.max_year {
+      params.push(("maxYear", year.to_string()));
+    }
+    if let Some(skip) = self.skip {
+      params.push(("skip", skip.to_string()));
+    }
+    if let Some(limit) = self.limit {
+      params.push(("limit", limit.to_string()));
+    }
+    params
+  }
+}
+
+/// The kinds of relationship the related words endpoint knows about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum RelationshipType {
+  Synonym,
+  Antonym,
+  Variant,
+  Equivalent,
+  CrossReference,
+  RelatedWord,
+  Rhyme,
+  Form,
+  EtymologicallyRelatedTerm,
+  Hypernym,
+  Hyponym,
+  InflectedForm,
+  Primary,
+  SameContext,
+  VerbForm,
+  VerbStem,
+  #[serde(rename = "has_topic")]
+  HasTopic,
+  /// A relationship type this crate doesn't know about yet.
+  #[serde(other)]
+  Unknown,
+}
+
+impl Display for RelationshipType {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match &self {
+      RelationshipType::Synonym => write!(f, "synonym"),
+      RelationshipType::Antonym => write!(f, "antonym"),
+      RelationshipType::Variant => write!(f, "variant"),
+      RelationshipType::Equivalent => write!(f, "equivalent"),
+      RelationshipType::CrossReference => write!(f, "cross-reference"),
+      RelationshipType::RelatedWord => write!(f, "related-word"),
+      RelationshipType::Rhyme => write!(f, "rhyme"),
+      RelationshipType::Form => write!(f, "form"),
+      RelationshipType::EtymologicallyRelatedTerm => {
+        write!(f, "etymologically-related-term")
+      }
+      RelationshipType::Hypernym => write!(f, "hypernym"),
+      RelationshipType::Hyponym => write!(f, "hyponym"),
+      RelationshipType::InflectedForm => write!(f, "inflected-form"),
+      RelationshipType::Primary => write!(f, "primary"),
+      RelationshipType::SameContext => write!(f, "same-context"),
+      RelationshipType::VerbForm => write!(f, "verb-form"),
+      RelationshipType::VerbStem => write!(f, "verb-stem"),
+      RelationshipType::HasTopic => write!(f, "has_topic"),
+      RelationshipType::Unknown => write!(f, "unknown"),
+    }
+  }
+}
+
+/// The struct representing API response for one group of related words.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedWords {
+  pub relationship_type: RelationshipType,
+  #[serde(default)]
+  pub words: Vec<String>,
+}
+
+/// Optional parameters for
+/// [get_definitions_with](Wordnik::get_definitions_with).
+///
+/// # Example
+///
+/// ```
+/// use wordnik::DefinitionsQuery;
+///
+/// let query = DefinitionsQuery::new()
+///   .include_tags(true)
+///   .expand_terms(true)
+///   .dedup(true);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DefinitionsQuery {
+  include_tags: bool,
+  include_related: bool,
+  expand_terms: bool,
+  dedup: bool,
+  sort: SortOrder,
+  include_empty: bool,
+}
+
+impl DefinitionsQuery {
+  pub fn new() -> DefinitionsQuery {
+    DefinitionsQuery::default()
+  }
+
+  /// Keep the markup, such as `<xref>` cross-references, in
+  /// [Definition::definition]. [Definition::plain_text] strips it.
+  pub fn include_tags(mut self, include: bool) -> DefinitionsQuery {
+    self.include_tags = include;
+    self
+  }
+
+  /// Ask the API for the words related to each sense, in
+  /// [Definition::related_words].
+  pub fn include_related(mut self, include: bool) -> DefinitionsQuery {
+    self.include_related = include;
+    self
+  }
+
+  /// Ask the API to expand abbreviated terms in the text.
+  pub fn expand_terms(mut self, expand: bool) -> DefinitionsQuery {
+    self.expand_terms = expand;
+    self
+  }
+
+  /// Drop definitions repeating an earlier one, see [dedup_definitions].
+  /// Off by default.
+  pub fn dedup(mut self, dedup: bool) -> DefinitionsQuery {
+    self.dedup = dedup;
+    self
+  }
+
+  /// Keep definitions without text, which are dropped by default. They
+  /// may still have a part of speech, a source dictionary, related words
+  /// or, as in some Century entries, only [citations](Definition::citations).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{DefinitionsQuery, Wordnik};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |_: &str, _: &HeaderMap| {
+  ///     Ok(json!([{
+  ///       "word": "run",
+  ///       "partOfSpeech": "verb",
+  ///       "attributionText": "",
+  ///       "sourceDictionary": "century",
+  ///       "attributionUrl": "",
+  ///       "wordnikUrl": "",
+  ///       "citations": [{ "source": "Shak.", "cite": "Run, run, O run!" }]
+  ///     }]))
+  ///   },
+  /// );
+  /// assert!(api.get_definitions("run").unwrap().is_empty());
+  /// let query = DefinitionsQuery::new().include_empty(true);
+  /// let defs = api.get_definitions_with("run", &query).unwrap();
+  /// assert_eq!(defs[0].source_dictionary, "century");
+  /// assert_eq!(defs[0].definition, None);
+  /// assert_eq!(defs[0].citations[0].cite, "Run, run, O run!");
+  /// ```
+  pub fn include_empty(mut self, include: bool) -> DefinitionsQuery {
+    self.include_empty = include;
+    self
+  }
+
+  /// Order the definitions, see [sort_definitions]. By default they are
+  /// in the API's order.
+  pub fn sort(mut self, order: SortOrder) -> DefinitionsQuery {
+    self.sort = order;
+    self
+  }
+
+  fn params(&self) -> Vec<(&'static str, String)> {
+    let mut params = Vec::new();
+    if self.include_tags {
+      params.push(("includeTags", "true".to_string()));
+    }
+    if self.include_related {
+      params.push(("includeRelated", "true".to_string()));
+    }
+    if self.expand_terms {
+      params.push(("expandTerms", "true".to_string()));
+    }
+    params
+  }
+}
+
+/// Optional parameters for
+/// [get_related_words_with](Wordnik::get_related_words_with).
+///
+/// # Example
+///
+/// ```
+/// use wordnik::{RelatedWordsQuery, RelationshipType};
+///
+/// let query = RelatedWordsQuery::new()
+///   .relationship_types(&[RelationshipType::Synonym, RelationshipType::Antonym])
+///   .limit_per_relationship_type(10);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RelatedWordsQuery {
+  relationship_types: Vec<RelationshipType>,
+  limit_per_relationship_type: Option<u32>,
+}
+
+impl RelatedWordsQuery {
+  pub fn new() -> RelatedWordsQuery {
+    RelatedWordsQuery::default()
+  }
+
+  /// Only return these relationship types. All are returned if empty.
+  pub fn relationship_types(
+    mut self,
+    types: &[RelationshipType],
+  ) -> RelatedWordsQuery {
+    self.relationship_types = types.to_vec();
+    self
+  }
+
+  /// Return at most `limit` words per relationship type.
+  pub fn limit_per_relationship_type(
+    mut self,
+    limit: u32,
+  ) -> RelatedWordsQuery {
+    self.limit_per_relationship_type = Some(limit);
+    self
+  }
+
+  fn params(&self) -> Vec<(&'static str, String)> {
+    let mut params = Vec::new();
+    if !self.relationship_types.is_empty() {
+      let types: Vec<String> = self
+        .relationship_types
+        .iter()
+        .map(|t| t.to_string())
+        .collect();
+      params.push(("relationshipTypes", types.join(",")));
+    }
+    if let Some(limit) = self.limit_per_relationship_type {
+      params.push(("limitPerRelationshipType", limit.to_string()));
+    }
+    params
+  }
+}
+
+/// Definitions, examples and pronunciations of a word, fetched together
+/// by [get_word_details](Wordnik::get_word_details).
+///
+/// Like every response type, it serializes to the JSON it was read from,
+/// so lookups can be cached and loaded again.
+///
+/// # Example
+///
+/// ```
+/// use wordnik::{Definition, Example, Pronunciation, WordDetails};
+///
+/// let details = WordDetails {
+///   definitions: vec![Definition {
+///     definition: Some("A unit of language.".to_string()),
+///     ..Definition::default()
+///   }],
+///   examples: vec![Example {
+///     text: "A word to the wise.".to_string(),
+///     year: Some(2004),
+///     ..Example::default()
+///   }],
+///   pronunciations: vec![Pronunciation {
+///     raw: "/wɜːd/".to_string(),
+///     raw_type: "IPA".to_string(),
+///     ..Pronunciation::default()
+///   }],
+/// };
+/// let json = serde_json::to_string(&details).unwrap();
+/// assert!(json.contains(r#""text":"A unit of language.""#));
+/// let cached: WordDetails = serde_json::from_str(&json).unwrap();
+/// assert_eq!(cached, details);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WordDetails {
+  pub definitions: Vec<Definition>,
+  pub examples: Vec<Example>,
+  pub pronunciations: Vec<Pronunciation>,
+}
+
+impl WordDetails {
+  /// Write the details to the file at `path` as JSON, replacing it, e.g.
+  /// to build a dictionary that works offline.
+  ///
+  /// Files written by older versions of the crate load with the fields
+  /// they lack left empty, and newer files load with the fields this
+  /// version doesn't know about ignored.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wordnik::{Definition, WordDetails, WordnikError};
+  ///
+  /// let details = WordDetails {
+  ///   definitions: vec![Definition {
+  ///     definition: Some("A unit of language.".to_string()),
+  ///     ..Definition::default()
+  ///   }],
+  ///   ..WordDetails::default()
+  /// };
+  /// let path = std::env::temp_dir().join("wordnik-doctest-word.json");
+  /// details.save_to(&path).unwrap();
+  /// assert_eq!(WordDetails::load_from(&path).unwrap(), details);
+  ///
+  /// std::fs::write(&path, r#"{"definitions": [], "synonyms": []}"#).unwrap();
+  /// assert_eq!(WordDetails::load_from(&path).unwrap(), WordDetails::default());
+  /// std::fs::remove_file(&path).unwrap();
+  ///
+  /// let err = WordDetails::load_from(&path).unwrap_err();
+  /// assert!(matches!(err, WordnikError::File(p, _) if p == path));
+  /// ```
+  pub fn save_to(
+    &self,
+    path: impl AsRef<std::path::Path>,
+  ) -> Result<(), WordnikError> {
+    let path = path.as_ref();
+    let json = serde_json::to_string(self)?;
+    std::fs::write(path, json)
+      .map_err(|e| WordnikError::File(path.to_path_buf(), e))
+  }
+
+  /// Read details written by [save_to](Self::save_to).
+  pub fn load_from(
+    path: impl AsRef<std::path::Path>,
+  ) -> Result<WordDetails, WordnikError> {
+    let path = path.as_ref();
+    let json = std::fs::read_to_string(path)
+      .map_err(|e| WordnikError::File(path.to_path_buf(), e))?;
+    Ok(serde_json::from_str(&json)?)
+  }
+}
+
+/// The struct representing API response for word frequency.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Frequency {
+  #[serde(default)]
+  pub word: String,
+  #[serde(default, deserialize_with = "de::lenient_number")]
+  pub total_count: u64,
+  /// Occurrences whose year is not known.
+  #[serde(default, deserialize_with = "de::lenient_number")]
+  pub unknown_year_count: u64,
+  #[serde(default)]
+  pub frequency: Vec<YearFrequency>,
+}
+
+impl Frequency {
+  /// The occurrences from `start_year` to `end_year`, both included.
+  /// Occurrences of unknown year are left out; add
+  /// [unknown_year_count](Self::unknown_year_count) to count them too.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wordnik::{Frequency, YearFrequency};
+  ///
+  /// let year = |year, count| YearFrequency { year, count };
+  /// let frequency = Frequency {
+  ///   unknown_year_count: 100,
+  ///   frequency: vec![year(1990, 3), year(1991, 7), year(1992, 7), year(1993, 1)],
+  ///   ..Frequency::default()
+  /// };
+  /// assert_eq!(frequency.total_in_range(1991, 1993), 15);
+  /// assert_eq!(frequency.total_in_range(2000, 2010), 0);
+  /// assert_eq!(frequency.peak_year(), Some(1991));
+  /// assert_eq!(Frequency::default().peak_year(), None);
+  /// ```
+  pub fn total_in_range(&self, start_year: i32, end_year: i32) -> u64 {
+    self
+      .frequency
+      .iter()
+      .filter(|f| (start_year..=end_year).contains(&f.year))
+      .map(|f| f.count)
+      .sum()
+  }
+
+  /// The year with the most occurrences, the earliest on a tie, or `None`
+  /// if there are no years.
+  pub fn peak_year(&self) -> Option<i32> {
+    let peak = self
+      .frequency
+      .iter()
+      .min_by_key(|f| (std::cmp::Reverse(f.count), f.year))?;
+    Some(peak.year)
+  }
+}
+
+/// The number of occurrences of a word in one year.
+///
+/// Both fields accept numbers sent as strings, and fall back to 0 when
+/// missing or malformed.
+///
+/// # Example
+///
+/// ```
+/// use wordnik::YearFrequency;
+///
+/// let v: Vec<YearFrequency> = serde_json::from_str(
+///   r#"[{"year": "1990", "count": 3}, {"year": 1991, "count": null}]"#,
+/// )
+/// .unwrap();
+/// assert_eq!(v[0], YearFrequency { year: 1990, count: 3 });
+/// assert_eq!(v[1], YearFrequency { year: 1991, count: 0 });
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YearFrequency {
+  #[serde(default, deserialize_with = "de::lenient_number")]
+  pub year: i32,
+  #[serde(default, deserialize_with = "de::lenient_number")]
+  pub count: u64,
+}
+
+/// The most suggestions
+/// [spelling_suggestions](Wordnik::spelling_suggestions) returns.
+pub const SUGGESTION_LIMIT: usize = 5;
+
+/// The most rhymes [get_rhymes](Wordnik::get_rhymes) asks for.
+pub const RHYME_LIMIT: u32 = 1000;
+
+/// What [get_definitions_map](Wordnik::get_definitions_map) does with
+/// words the API has no entry for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingWords {
+  /// Leave them out of the map.
+  #[default]
+  Skip,
+  /// Map them to no definitions.
+  Empty,
+}
+
+/// How common a word is, as classified by [FrequencyThresholds].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FrequencyBand {
+  Common,
+  Uncommon,
+  Rare,
+}
+
+/// The [total counts](Frequency::total_count) from which words are
+/// [Common](FrequencyBand::Common) or [Uncommon](FrequencyBand::Uncommon);
+/// words below both are [Rare](FrequencyBand::Rare).
+///
+/// The defaults are a rough split of the counts the API reports; tune
+/// them to your texts if needed.
+///
+/// # Example
+///
+/// ```
+/// use wordnik::{FrequencyBand, FrequencyThresholds};
+///
+/// let thresholds = FrequencyThresholds::default();
+/// assert_eq!(thresholds.band(thresholds.common), FrequencyBand::Common);
+/// assert_eq!(thresholds.band(thresholds.common - 1), FrequencyBand::Uncommon);
+/// assert_eq!(thresholds.band(0), FrequencyBand::Rare);
+///
+/// let strict = FrequencyThresholds { common: 1_000_000, uncommon: 10_000 };
+/// assert_eq!(strict.band(50_000), FrequencyBand::Uncommon);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrequencyThresholds {
+  pub common: u64,
+  pub uncommon: u64,
+}
+
+impl Default for FrequencyThresholds {
+  fn default() -> FrequencyThresholds {
+    FrequencyThresholds {
+      common: 10_000,
+      uncommon: 500,
+    }
+  }
+}
+
+impl FrequencyThresholds {
+  /// The band of a word occurring `total_count` times.
+  pub fn band(&self, total_count: u64) -> FrequencyBand {
+    if total_count >= self.common {
+      FrequencyBand::Common
+    } else if total_count >= self.uncommon {
+      FrequencyBand::Uncommon
+    } else {
+      FrequencyBand::Rare
+    }
+  }
+}
+
+/// Optional parameters for [get_frequency_with](Wordnik::get_frequency_with).
+///
+/// # Example
+///
+/// ```
+/// use wordnik::FrequencyQuery;
+///
+/// let query = FrequencyQuery::new().start_year(1900).end_year(2000);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct FrequencyQuery {
+  start_year: Option<i32>,
+  end_year: Option<i32>,
+}
+
+impl FrequencyQuery {
+  pub fn new() -> FrequencyQuery {
+    FrequencyQuery::default()
+  }
+
+  /// Only count occurrences from this year or later.
+  pub fn start_year(mut self, year: i32) -> FrequencyQuery {
+    self.start_year = Some(year);
+    self
+  }
+
+  /// Only count occurrences from this year or earlier.
+  pub fn end_year(mut self, year: i32) -> FrequencyQuery {
+    self.end_year = Some(year);
+    self
+  }
+
+  fn params(&self) -> Vec<(&'static str, String)> {
+    let mut params = Vec::new();
+    if let Some(year) = self.start_year {
+      params.push(("startYear", year.to_string()));
+    }
+    if let Some(year) = self.end_year {
+      params.push(("endYear", year.to_string()));
+    }
+    params
+  }
+}
+
+/// The struct representing API response for one syllable of a word.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Syllable {
+  pub text: String,
+  #[serde(default)]
+  pub seq: i64,
+  /// The stress of the syllable, e.g. "stress" or "secondary stress".
+  #[serde(default, rename = "type")]
+  pub syllable_type: Option<String>,
+}
+
+/// A two-word phrase a word appears in, as returned by
+/// [get_phrases](Wordnik::get_phrases).
+///
+/// # Example
+///
+/// ```
+/// use wordnik::Phrase;
+///
+/// let phrase = Phrase {
+///   gram1: "word".to_string(),
+///   gram2: "processing".to_string(),
+///   count: 42,
+///   ..Phrase::default()
+/// };
+/// let json = serde_json::to_string(&phrase).unwrap();
+/// assert_eq!(serde_json::from_str::<Phrase>(&json).unwrap(), phrase);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Phrase {
+  pub gram1: String,
+  pub gram2: String,
+  /// How often the phrase occurs.
+  #[serde(default, deserialize_with = "de::lenient_number")]
+  pub count: u64,
+  /// The mutual information of the two words.
+  #[serde(default)]
+  pub mi: f64,
+  /// The weighted log mutual information of the two words, which favours
+  /// common phrases over rare ones.
+  #[serde(default)]
+  pub wlmi: f64,
+}
+
+#[cfg(feature = "blocking")]
+impl Wordnik {
+  /// Create a new API endpoint.
+  ///
+  /// A trailing slash is appended to `entry` if it is missing; use
+  /// [try_new](Self::try_new) to also validate the arguments.
+  ///
+  /// The underlying HTTP client is built once here and reused by every
+  /// request, so connections and TLS sessions are pooled across calls. It
+  /// identifies itself with [DEFAULT_USER_AGENT].
+  pub fn new(api_key: String, entry: String) -> Wordnik {
+    Wordnik {
+      config: Config::new_unchecked(api_key, entry),
+      requester: Arc::new(builder::default_blocking_client()),
+      hooks: Hooks::default(),
+      cache: Caches::default(),
+      bypass_cache: false,
+      rate_limits: RateLimits::default(),
+    }
+  }
+
+  /// Create a new API endpoint, validating the arguments.
+  ///
+  /// Unlike [new](Self::new), this rejects an empty API key, and an entry
+  /// that isn't an `http` or `https` URL or that carries a query string.
+  /// The entry is expected to point at the `word.json/` resource, e.g.
+  /// [DEFAULT_API_URL]; the trailing slash is appended if it is missing.
+  ///
+  /// # Example
+  /// ```
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let api = Wordnik::try_new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json".to_string(),
+  /// )
+  /// .unwrap();
+  /// assert_eq!(api.entry(), "https://api.wordnik.com/v4/word.json/");
+  ///
+  /// let api = Wordnik::try_new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// )
+  /// .unwrap();
+  /// assert_eq!(api.entry(), "https://api.wordnik.com/v4/word.json/");
+  ///
+  /// for bogus in ["", "word.json", "ftp://example.com/", "http://a/?x=1"] {
+  ///   let api = Wordnik::try_new("YOUR_API_KEY".to_string(), bogus.to_string());
+  ///   assert!(matches!(api, Err(WordnikError::InvalidEntry(_))));
+  /// }
+  ///
+  /// let api = Wordnik::try_new(
+  ///   "".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  /// assert!(matches!(api, Err(WordnikError::EmptyApiKey)));
+  /// ```
+  pub fn try_new(
+    api_key: String,
+    entry: String,
+  ) -> Result<Wordnik, WordnikError> {
+    Ok(Wordnik {
+      config: Config::new(api_key, entry)?,
+      requester: Arc::new(builder::default_blocking_client()),
+      hooks: Hooks::default(),
+      cache: Caches::default(),
+      bypass_cache: false,
+      rate_limits: RateLimits::default(),
+    })
+  }
+
+  /// Create a [WordnikBuilder], which defaults to [DEFAULT_API_URL].
+  pub fn builder() -> WordnikBuilder {
+    WordnikBuilder::new()
+  }
+
+  /// Create a new API endpoint from the environment.
+  ///
+  /// The API key is read from `WORDNIK_API_KEY`, and the base URL from
+  /// `WORDNIK_API_URL`, falling back to [DEFAULT_API_URL]. Surrounding
+  /// whitespace, such as a trailing newline, is trimmed.
+  ///
+  /// # Example
+  ///
+  /// The environment is shared by the whole process, so changes to it are
+  /// made while holding a lock:
+  ///
+  /// ```
+  /// use std::sync::Mutex;
+  /// use wordnik::{Wordnik, WordnikError, DEFAULT_API_URL};
+  ///
+  /// static ENV: Mutex<()> = Mutex::new(());
+  /// let _env = ENV.lock().unwrap();
+  ///
+  /// std::env::remove_var("WORDNIK_API_KEY");
+  /// std::env::remove_var("WORDNIK_API_URL");
+  /// let res = Wordnik::from_env();
+  /// assert!(matches!(res, Err(WordnikError::MissingEnvVar("WORDNIK_API_KEY"))));
+  ///
+  /// std::env::set_var("WORDNIK_API_KEY", " \n");
+  /// let res = Wordnik::from_env();
+  /// assert!(matches!(res, Err(WordnikError::MissingEnvVar("WORDNIK_API_KEY"))));
+  ///
+  /// std::env::set_var("WORDNIK_API_KEY", "YOUR_API_KEY\n");
+  /// assert_eq!(Wordnik::from_env().unwrap().entry(), DEFAULT_API_URL);
+  ///
+  /// std::env::set_var("WORDNIK_API_URL", "http://localhost:8080/v4/word.json\n");
+  /// let api = Wordnik::from_env().unwrap();
+  /// assert_eq!(api.entry(), "http://localhost:8080/v4/word.json/");
+  ///
+  /// std::env::set_var("WORDNIK_API_URL", "not a url");
+  /// assert!(matches!(Wordnik::from_env(), Err(WordnikError::InvalidEntry(_))));
+  /// ```
+  pub fn from_env() -> Result<Wordnik, WordnikError> {
+    WordnikBuilder::from_env()?.build()
+  }
+
+  /// Create a new API endpoint that sends every request through `client`.
+  ///
+  /// Use this to share a connection pool, or to apply proxies, root
+  /// certificates and default headers you have already configured.
+  ///
+  /// # Example
+  /// ```
+  /// use wordnik::Wordnik;
+  ///
+  /// let client = reqwest::blocking::Client::builder()
+  ///   .timeout(std::time::Duration::from_secs(10))
+  ///   .build()
+  ///   .unwrap();
+  /// let api = Wordnik::with_client(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   client,
+  /// );
+  /// ```
+  ///
+  /// A body that stops arriving times out like the rest of the request:
+  ///
+  /// ```
+  /// use std::io::{BufRead, BufReader, Write};
+  /// use std::net::TcpListener;
+  /// use std::time::Duration;
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let server = TcpListener::bind("127.0.0.1:0").unwrap();
+  /// let base_url = format!("http://{}/", server.local_addr().unwrap());
+  /// std::thread::spawn(move || {
+  ///   let (stream, _) = server.accept().unwrap();
+  ///   let mut reader = BufReader::new(&stream);
+  ///   let mut line = String::new();
+  ///   while reader.read_line(&mut line).unwrap() > 2 {
+  ///     line.clear();
+  ///   }
+  ///   let mut stream = &stream;
+  ///   write!(stream, "HTTP/1.1 200 OK\r\ncontent-length: 100\r\n\r\n[").unwrap();
+  ///   std::thread::sleep(Duration::from_secs(2));
+  /// });
+  ///
+  /// let client = reqwest::blocking::Client::builder()
+  ///   .timeout(Duration::from_millis(200))
+  ///   .build()
+  ///   .unwrap();
+  /// let api = Wordnik::with_client("YOUR_API_KEY".to_string(), base_url, client);
+  /// let err = api.get_definitions("word").unwrap_err();
+  /// assert!(matches!(err, WordnikError::Http(e) if e.is_timeout()));
+  /// ```
+  pub fn with_client(
+    api_key: String,
+    entry: String,
+    client: reqwest::blocking::Client,
+  ) -> Wordnik {
+    Wordnik::with_requester(api_key, entry, client)
+  }
+
+  /// Create a new API endpoint that sends every request through
+  /// `requester`.
+  ///
+  /// See [Requester] for an example serving canned responses.
+  pub fn with_requester(
+    api_key: String,
+    entry: String,
+    requester: impl Requester + 'static,
+  ) -> Wordnik {
+    Wordnik {
+      config: Config::new_unchecked(api_key, entry),
+      requester: Arc::new(requester),
+      hooks: Hooks::default(),
+      cache: Caches::default(),
+      bypass_cache: false,
+      rate_limits: RateLimits::default(),
+    }
+  }
+
+  /// Replay responses recorded in `dir`, one JSON file per request, so
+  /// tests run without network access or an API key.
+  ///
+  /// A request without a recording is sent and its response recorded
+  /// with [FixtureMode::Record], and fails with [WordnikError::Fixture]
+  /// with [FixtureMode::Replay]. Files are named after the word,
+  /// operation and parameters, and never contain the API key. 404
+  /// responses are recorded too.
+  ///
+  /// Requires the `fixtures` feature.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::{FixtureMode, Wordnik};
+  ///
+  /// // Record once with a real key, then commit tests/fixtures.
+  /// let api = Wordnik::new(
+  ///   std::env::var("WORDNIK_API_KEY").unwrap_or_default(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// )
+  /// .with_fixture_dir("tests/fixtures", FixtureMode::Record);
+  /// let v = api.get_definitions("word").unwrap();
+  /// ```
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::{FixtureMode, Wordnik, WordnikError};
+  /// let dir = std::env::temp_dir().join("wordnik-fixtures-doctest");
+  /// # let _ = std::fs::remove_dir_all(&dir);
+  /// let online = |url: &str, _: &HeaderMap| {
+  ///   assert!(url.contains("/word/"));
+  ///   Ok(json!([{ "text": "word", "seq": 0 }]))
+  /// };
+  /// let offline = |_: &str, _: &HeaderMap| -> Result<_, WordnikError> {
+  ///   panic!("went online");
+  /// };
+  /// let entry = "https://api.wordnik.com/v4/word.json/".to_string();
+  ///
+  /// let api = Wordnik::with_requester("SECRET".to_string(), entry.clone(), online)
+  ///   .with_fixture_dir(&dir, FixtureMode::Record);
+  /// api.get_hyphenation("word").unwrap();
+  ///
+  /// let api = Wordnik::with_requester("".to_string(), entry, offline)
+  ///   .with_fixture_dir(&dir, FixtureMode::Replay);
+  /// let v = api.get_hyphenation("word").unwrap();
+  /// assert_eq!(v[0].text, "word");
+  /// assert!(matches!(
+  ///   api.get_hyphenation("other"),
+  ///   Err(WordnikError::Fixture(..)),
+  /// ));
+  ///
+  /// for file in std::fs::read_dir(&dir).unwrap() {
+  ///   let json = std::fs::read_to_string(file.unwrap().path()).unwrap();
+  ///   assert!(!json.contains("SECRET"));
+  /// }
+  /// ```
+  #[cfg(feature = "fixtures")]
+  pub fn with_fixture_dir(
+    self,
+    dir: impl AsRef<std::path::Path>,
+    mode: FixtureMode,
+  ) -> Wordnik {
+    let fixtures =
+      fixtures::Fixtures::new(dir.as_ref().to_path_buf(), mode, self.requester);
+    Wordnik {
+      requester: Arc::new(fixtures),
+      ..self
+    }
+  }
+
+  /// The entry URL requests are built from, always ending with a slash.
+  pub fn entry(&self) -> &str {
+    &self.config.entry
+  }
+
+  fn execute<T>(&self, call: Call<T>) -> Result<T, WordnikError> {
+    #[cfg(feature = "tracing")]
+    let _span = call.span().entered();
+    let mut parts = RequestParts {
+      url: self.config.url(&call)?,
+      headers: self.config.headers()?,
+    };
+    let key = self.config.cache_key(&call)?;
+    if !self.bypass_cache {
+      if let Some(res) = self.cache.before(&key, &mut parts.headers)? {
+        return call.parse(res);
+      }
+    }
+    if self.cache.offline {
+      return Err(WordnikError::Offline);
+    }
+    self.hooks.before(&mut parts);
+    let mut retries = 0;
+    let res = loop {
+      self.rate_limits.wait();
+      let start = Instant::now();
+      let res = self
+        .requester
+        .request_conditional(&parts.url, &parts.headers);
+      self.rate_limits.record(&res);
+      self
+        .hooks
+        .after(&ResponseMeta::new(&parts.url, start.elapsed(), &res));
+      match self.config.retry.delay(retries, &res) {
+        Some(delay) => std::thread::sleep(delay),
+        None => break res,
+      }
+      retries += 1;
+    };
+    call.parse(self.cache.after(&key, res)?)
+  }
+
+  /// The rate limits the API reported with the latest response sent by
+  /// this client or its clones, e.g. to slow down before running out.
+  /// `None` until a response reports them; responses from the cache
+  /// don't.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::{HeaderMap, HeaderValue};
+  /// # use serde_json::{json, Value};
+  /// use wordnik::{Fetched, RateLimitStatus, Requester, Wordnik, WordnikError};
+  ///
+  /// struct Server;
+  ///
+  /// impl Requester for Server {
+  ///   fn request(&self, _: &str, _: &HeaderMap) -> Result<Value, WordnikError> {
+  ///     unreachable!()
+  ///   }
+  ///
+  ///   fn request_conditional(
+  ///     &self,
+  ///     _: &str,
+  ///     _: &HeaderMap,
+  ///   ) -> Result<Fetched, WordnikError> {
+  ///     let mut headers = HeaderMap::new();
+  ///     headers.insert("x-ratelimit-limit-minute", HeaderValue::from_static("15"));
+  ///     headers.insert("x-ratelimit-remaining-minute", HeaderValue::from_static("3"));
+  ///     Ok(Fetched::Body {
+  ///       body: json!([]),
+  ///       etag: None,
+  ///       rate_limit: RateLimitStatus::from_headers(&headers),
+  ///     })
+  ///   }
+  /// }
+  ///
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .requester(Server)
+  ///   .build()
+  ///   .unwrap();
+  /// assert_eq!(api.last_rate_limit(), None);
+  /// api.get_hyphenation("word").unwrap();
+  ///
+  /// let status = api.last_rate_limit().unwrap();
+  /// assert_eq!(status.limit_minute, Some(15));
+  /// assert_eq!(status.remaining_minute, Some(3));
+  /// ```
+  pub fn last_rate_limit(&self) -> Option<RateLimitStatus> {
+    self.rate_limits.last()
+  }
+
+  /// Forget all cached responses, in memory and on disk. Does nothing if
+  /// the cache is off.
+  ///
+  /// See [WordnikBuilder::cache].
+  pub fn clear_cache(&self) {
+    self.cache.clear();
+  }
+
+  /// Get a handle to the same endpoint that doesn't look in the cache, for
+  /// when fresh data is needed. Its responses still update the cache.
+  ///
+  /// See [WordnikBuilder::cache].
+  pub fn bypass_cache(&self) -> Wordnik {
+    Wordnik {
+      config: self.config.clone(),
+      requester: self.requester.clone(),
+      hooks: self.hooks.clone(),
+      cache: self.cache.clone(),
+      bypass_cache: true,
+      rate_limits: self.rate_limits.clone(),
+    }
+  }
+
+  /// Look up the word itself, resolving inflections to their canonical
+  /// form and suggesting spellings for unknown words.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let word = api.get_word("running").unwrap();
+  /// let base = word.canonical_form.unwrap_or(word.word);
+  /// println!("{:#?}", api.get_definitions(&base).unwrap());
+  /// ```
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::Wordnik;
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert_eq!(
+  ///       url,
+  ///       "https://api.wordnik.com/v4/word.json/running?useCanonical=true&includeSuggestions=true",
+  ///     );
+  ///     Ok(json!({ "id": 0, "word": "run", "canonicalForm": "run", "originalWord": "running" }))
+  ///   },
+  /// );
+  /// let word = api.get_word("running").unwrap();
+  /// assert_eq!(word.canonical_form.as_deref(), Some("run"));
+  /// assert!(word.suggestions.is_empty());
+  /// ```
+  pub fn get_word(&self, word: &str) -> Result<WordObject, WordnikError> {
+    self.execute(call::word(word))
+  }
+
+  /// Get the spellings the API suggests for a word, e.g. after a lookup
+  /// failed with [NotFound](WordnikError::NotFound). At most
+  /// [SUGGESTION_LIMIT] are returned, without repeats or the word itself
+  /// in any case, and none if the API has no suggestions.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     if url.contains("/definitions") {
+  ///       return Err(WordnikError::NotFound);
+  ///     }
+  ///     Ok(json!({
+  ///       "word": "definately",
+  ///       "suggestions": [
+  ///         "definitely", "Definately", "Definitely", "defiantly", "definitely"
+  ///       ]
+  ///     }))
+  ///   },
+  /// );
+  /// let res = api.get_definitions("definately");
+  /// assert!(matches!(res, Err(WordnikError::NotFound)));
+  /// let suggestions = api.spelling_suggestions("definately").unwrap();
+  /// assert_eq!(suggestions, ["definitely", "defiantly"]);
+  /// ```
+  pub fn spelling_suggestions(
+    &self,
+    word: &str,
+  ) -> Result<Vec<String>, WordnikError> {
+    match self.get_word(word) {
+      Ok(found) => Ok(call::suggestions(found, word)),
+      Err(WordnikError::NotFound) => Ok(Vec::new()),
+      Err(e) => Err(e),
+    }
+  }
+
+  /// Get definitions of a word. Definitions without text are dropped; to
+  /// keep them, see [DefinitionsQuery::include_empty].
+  ///
+  /// For a pretty string of definitions,
+  /// see [get_definitions_pretty](Self::get_definitions_pretty). To drop
+  /// definitions repeated across dictionaries, see [dedup_definitions].
+  ///
+  /// The word is percent-encoded, so phrases like "ice cream" and words
+  /// with non-ASCII letters or slashes are looked up as-is.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let v = api.get_definitions("word").unwrap();
+  /// println!("{:#?}", v);
+  /// ```
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::{json, Value};
+  /// # use std::sync::{Arc, Mutex};
+  /// # use wordnik::{Requester, Wordnik, WordnikError};
+  /// # #[derive(Clone, Default)]
+  /// # struct Recorder(Arc<Mutex<Vec<String>>>);
+  /// # impl Requester for Recorder {
+  /// #   fn request(&self, url: &str, _: &HeaderMap) -> Result<Value, WordnikError> {
+  /// #     self.0.lock().unwrap().push(url.to_string());
+  /// #     Ok(json!([]))
+  /// #   }
+  /// # }
+  /// # let recorder = Recorder::default();
+  /// # let api = Wordnik::with_requester(
+  /// #   "KEY".to_string(),
+  /// #   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// #   recorder.clone(),
+  /// # );
+  /// for word in ["ice cream", "naïve", "coup d'état", "../admin", "a/b"] {
+  ///   api.get_definitions(word).unwrap();
+  /// }
+  /// assert!(matches!(api.get_definitions(".."), Err(WordnikError::InvalidWord(_))));
+  ///
+  /// let urls = recorder.0.lock().unwrap();
+  /// let base = "https://api.wordnik.com/v4/word.json/";
+  /// assert_eq!(urls[0], format!("{base}ice%20cream/definitions"));
+  /// assert_eq!(urls[1], format!("{base}na%C3%AFve/definitions"));
+  /// assert_eq!(urls[2], format!("{base}coup%20d'%C3%A9tat/definitions"));
+  /// assert_eq!(urls[3], format!("{base}..%2Fadmin/definitions"));
+  /// assert_eq!(urls[4], format!("{base}a%2Fb/definitions"));
+  /// ```
+  pub fn get_definitions(
+    &self,
+    word: &str,
+  ) -> Result<Vec<Definition>, WordnikError> {
+    self.get_definitions_with(word, &DefinitionsQuery::default())
+  }
+
+  /// Get definitions of a word, as asked for by `query`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{DefinitionsQuery, Wordnik};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert_eq!(
+  ///       url,
+  ///       "https://api.wordnik.com/v4/word.json/run/definitions?includeTags=true",
+  ///     );
+  ///     Ok(json!([{
+  ///       "word": "run",
+  ///       "text": "To <xref>move</xref> swiftly.",
+  ///       "attributionText": "",
+  ///       "sourceDictionary": "",
+  ///       "attributionUrl": "",
+  ///       "wordnikUrl": ""
+  ///     }]))
+  ///   },
+  /// );
+  /// let query = DefinitionsQuery::new().include_tags(true);
+  /// let defs = api.get_definitions_with("run", &query).unwrap();
+  /// assert_eq!(defs[0].definition.as_deref(), Some("To <xref>move</xref> swiftly."));
+  /// assert_eq!(defs[0].plain_text(), "To move swiftly.");
+  /// ```
+  ///
+  /// With [include_related](DefinitionsQuery::include_related), each
+  /// sense comes with its related words, and often its example uses.
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{DefinitionsQuery, Wordnik};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert!(url.ends_with("/definitions?includeRelated=true"));
+  ///     Ok(json!([
+  ///       {
+  ///         "word": "run",
+  ///         "text": "To move swiftly on foot.",
+  ///         "exampleUses": [{ "text": "ran to catch the bus" }],
+  ///         "attributionText": "",
+  ///         "sourceDictionary": "",
+  ///         "attributionUrl": "",
+  ///         "wordnikUrl": ""
+  ///       },
+  ///       {
+  ///         "word": "run",
+  ///         "text": "To flee.",
+  ///         "attributionText": "",
+  ///         "sourceDictionary": "",
+  ///         "attributionUrl": "",
+  ///         "wordnikUrl": ""
+  ///       },
+  ///     ]))
+  ///   },
+  /// );
+  /// let query = DefinitionsQuery::new().include_related(true);
+  /// let defs = api.get_definitions_with("run", &query).unwrap();
+  /// assert_eq!(defs[0].example_uses[0].text, "ran to catch the bus");
+  /// assert!(defs[1].example_uses.is_empty());
+  /// ```
+  pub fn get_definitions_with(
+    &self,
+    word: &str,
+    query: &DefinitionsQuery,
+  ) -> Result<Vec<Definition>, WordnikError> {
+    self.execute(call::definitions(word, query))
+  }
+
+  /// Get definitions of many words, with up to `concurrency` requests in
+  /// flight at once; [DEFAULT_CONCURRENCY] is a polite choice. The results
+  /// are in the order of `words`, and a failed lookup doesn't stop the
+  /// others.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::{json, Value};
+  /// use wordnik::{Wordnik, WordnikError, DEFAULT_CONCURRENCY};
+  ///
+  /// # let requester = |url: &str, _: &HeaderMap| -> Result<Value, WordnikError> {
+  /// #   let word = url.rsplit('/').nth(1).unwrap();
+  /// #   if word == "xyzzy" {
+  /// #     return Err(WordnikError::NotFound);
+  /// #   }
+  /// #   Ok(json!([{
+  /// #     "word": word,
+  /// #     "text": format!("The word {}.", word),
+  /// #     "attributionText": "",
+  /// #     "sourceDictionary": "",
+  /// #     "attributionUrl": "",
+  /// #     "wordnikUrl": ""
+  /// #   }]))
+  /// # };
+  /// # let api = Wordnik::with_requester(
+  /// #   "KEY".to_string(),
+  /// #   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// #   requester,
+  /// # );
+  /// let words = ["apple", "xyzzy", "banana", "cherry", "damson", "elder"];
+  /// let results = api.get_definitions_batch(&words, DEFAULT_CONCURRENCY);
+  ///
+  /// let looked_up: Vec<&str> = results.iter().map(|(w, _)| w.as_str()).collect();
+  /// assert_eq!(looked_up, words);
+  /// assert!(matches!(results[1].1, Err(WordnikError::NotFound)));
+  /// let banana = results[2].1.as_ref().unwrap();
+  /// assert_eq!(banana[0].definition.as_deref(), Some("The word banana."));
+  /// assert_eq!(results.iter().filter(|(_, res)| res.is_ok()).count(), 5);
+  /// ```
+  pub fn get_definitions_batch(
+    &self,
+    words: &[&str],
+    concurrency: usize,
+  ) -> Vec<(String, Result<Vec<Definition>, WordnikError>)> {
+    batch::map_blocking(words, concurrency, |word| self.get_definitions(word))
+  }
+
+  /// Get definitions of each of `words`, one after the other, keeping the
+  /// failures alongside the successes.
+  ///
+  /// Each lookup is retried as configured with
+  /// [WordnikBuilder::retry]. To space the requests out, set a
+  /// [rate limit](WordnikBuilder::rate_limit). For concurrent lookups, see
+  /// [get_definitions_batch](Self::get_definitions_batch).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use std::time::{Duration, Instant};
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let api = Wordnik::builder()
+  ///   .api_key("YOUR_API_KEY")
+  ///   .rate_limit(1, Duration::from_millis(50))
+  ///   .requester(|url: &str, _: &HeaderMap| match url.contains("/xyzzy/") {
+  ///     true => Err(WordnikError::NotFound),
+  ///     false => Ok(json!([])),
+  ///   })
+  ///   .build()
+  ///   .unwrap();
+  /// let start = Instant::now();
+  /// let results = api.get_definitions_many(&["apple", "xyzzy", "banana"]);
+  /// assert!(start.elapsed() >= Duration::from_millis(100));
+  ///
+  /// assert_eq!(results[1].0, "xyzzy");
+  /// assert!(matches!(results[1].1, Err(WordnikError::NotFound)));
+  /// assert!(results[2].1.is_ok());
+  /// ```
+  pub fn get_definitions_many(
+    &self,
+    words: &[&str],
+  ) -> Vec<(String, Result<Vec<Definition>, WordnikError>)> {
+    words
+      .iter()
+      .map(|word| (word.to_string(), self.get_definitions(word)))
+      .collect()
+  }
+
+  /// Get definitions of each of `words`, one after the other, as a map
+  /// from the word to its definitions. Words the API has no entry for are
+  /// left out or mapped to an empty list, as `missing` says; any other
+  /// error stops the lookups and is returned. A word given twice is looked
+  /// up once.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::{json, Value};
+  /// use wordnik::{MissingWords, Wordnik, WordnikError};
+  ///
+  /// # let requester = |url: &str, _: &HeaderMap| -> Result<Value, WordnikError> {
+  /// #   let word = url.rsplit('/').nth(1).unwrap();
+  /// #   match word {
+  /// #     "xyzzy" => Err(WordnikError::NotFound),
+  /// #     "offline" => Err(WordnikError::Offline),
+  /// #     _ => Ok(json!([{
+  /// #       "word": word,
+  /// #       "text": format!("The word {}.", word),
+  /// #       "attributionText": "",
+  /// #       "sourceDictionary": "",
+  /// #       "attributionUrl": "",
+  /// #       "wordnikUrl": ""
+  /// #     }])),
+  /// #   }
+  /// # };
+  /// # let api = Wordnik::with_requester(
+  /// #   "KEY".to_string(),
+  /// #   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// #   requester,
+  /// # );
+  /// let words = ["apple", "xyzzy", "banana"];
+  /// let glossary = api.get_definitions_map(&words, MissingWords::Skip).unwrap();
+  /// assert_eq!(glossary.len(), 2);
+  /// assert_eq!(glossary["apple"][0].definition.as_deref(), Some("The word apple."));
+  /// assert!(!glossary.contains_key("xyzzy"));
+  ///
+  /// let glossary = api.get_definitions_map(&words, MissingWords::Empty).unwrap();
+  /// assert!(glossary["xyzzy"].is_empty());
+  ///
+  /// let res = api.get_definitions_map(&["apple", "offline"], MissingWords::Skip);
+  /// assert!(matches!(res, Err(WordnikError::Offline)));
+  /// ```
+  pub fn get_definitions_map(
+    &self,
+    words: &[&str],
+    missing: MissingWords,
+  ) -> Result<HashMap<String, Vec<Definition>>, WordnikError> {
+    let mut map = HashMap::new();
+    let mut seen = std::collections::HashSet::new();
+    for &word in words {
+      if !seen.insert(word) {
+        continue;
+      }
+      match self.get_definitions(word) {
+        Ok(definitions) => {
+          map.insert(word.to_string(), definitions);
+        }
+        Err(WordnikError::NotFound) => {
+          if missing == MissingWords::Empty {
+            map.insert(word.to_string(), Vec::new());
+          }
+        }
+        Err(e) => return Err(e),
+      }
+    }
+    Ok(map)
+  }
+
+  /// Get a row of an Anki import file for each of `words`, as made by
+  /// [anki_note] from the word's first `max_definitions` definitions and
+  /// its top example. Words are looked up like
+  /// [get_definitions_batch](Self::get_definitions_batch), two requests
+  /// per word; a word without examples gets a note without one.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::{json, Value};
+  /// use wordnik::{Wordnik, WordnikError, DEFAULT_CONCURRENCY};
+  ///
+  /// # let requester = |url: &str, _: &HeaderMap| -> Result<Value, WordnikError> {
+  /// #   if url.contains("/examples") {
+  /// #     return Ok(json!({ "examples": [{ "text": "A fine day." }] }));
+  /// #   }
+  /// #   Ok(json!([{
+  /// #     "word": "fine",
+  /// #     "text": "Of superior quality.",
+  /// #     "partOfSpeech": "adjective",
+  /// #     "attributionText": "",
+  /// #     "sourceDictionary": "",
+  /// #     "attributionUrl": "",
+  /// #     "wordnikUrl": ""
+  /// #   }]))
+  /// # };
+  /// # let api = Wordnik::with_requester(
+  /// #   "KEY".to_string(),
+  /// #   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// #   requester,
+  /// # );
+  /// let notes = api.get_anki_notes(&["fine"], 3, DEFAULT_CONCURRENCY);
+  /// let tsv: String = notes.into_iter().filter_map(|(_, res)| res.ok()).collect();
+  /// assert_eq!(
+  ///   tsv,
+  ///   "fine\t<ol><li><i>adjective</i> Of superior quality.</li></ol>\
+  ///    <p><i>A fine day.</i></p>\tadjective\n",
+  /// );
+  /// ```
+  pub fn get_anki_notes(
+    &self,
+    words: &[&str],
+    max_definitions: usize,
+    concurrency: usize,
+  ) -> Vec<(String, Result<String, WordnikError>)> {
+    batch::map_blocking(words, concurrency, |word| {
+      let definitions = self.get_definitions(word)?;
+      let query = ExamplesQuery::new().limit(1);
+      let examples = match self.get_examples_with(word, &query) {
+        Err(WordnikError::NotFound) => Vec::new(),
+        res => res?,
+      };
+      Ok(anki_note(
+        word,
+        &definitions,
+        examples.first(),
+        max_definitions,
+      ))
+    })
+  }
+
+  /// Get definitions of a multiword phrase such as "machine learning",
+  /// or an empty list if the API has no entry for it.
+  ///
+  /// Unlike [get_definitions](Self::get_definitions), which looks words
+  /// up as given, leading and trailing whitespace is trimmed and runs of
+  /// whitespace, including tabs and line breaks, become one space, so a
+  /// phrase pasted from text finds the same entry. A phrase made only of
+  /// whitespace is an [InvalidWord](WordnikError::InvalidWord) error.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use std::sync::{Arc, Mutex};
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// # let urls = Arc::new(Mutex::new(Vec::new()));
+  /// # let recorded = urls.clone();
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   move |url: &str, _: &HeaderMap| {
+  /// #   recorded.lock().unwrap().push(url.to_string());
+  ///     match url.contains("machine%20learning/") {
+  ///       true => Ok(json!([{
+  ///         "word": "machine learning",
+  ///         "text": "A branch of artificial intelligence.",
+  ///         "attributionText": "",
+  ///         "sourceDictionary": "wiktionary",
+  ///         "attributionUrl": "",
+  ///         "wordnikUrl": ""
+  ///       }])),
+  ///       false => Err(WordnikError::NotFound),
+  ///     }
+  ///   },
+  /// );
+  /// let defs = api.get_phrase_definitions(" machine\n  learning ").unwrap();
+  /// assert_eq!(defs[0].word, "machine learning");
+  /// assert!(api.get_phrase_definitions("well, well").unwrap().is_empty());
+  /// assert!(matches!(
+  ///   api.get_phrase_definitions(" \t"),
+  ///   Err(WordnikError::InvalidWord(_)),
+  /// ));
+  ///
+  /// let urls = urls.lock().unwrap();
+  /// let base = "https://api.wordnik.com/v4/word.json/";
+  /// assert_eq!(urls[0], format!("{base}machine%20learning/definitions"));
+  /// assert_eq!(urls[1], format!("{base}well,%20well/definitions"));
+  /// assert_eq!(urls.len(), 2);
+  /// ```
+  pub fn get_phrase_definitions(
+    &self,
+    phrase: &str,
+  ) -> Result<Vec<Definition>, WordnikError> {
+    match self.get_definitions(&call::normalize_phrase(phrase)) {
+      Err(WordnikError::NotFound) => Ok(Vec::new()),
+      res => res,
+    }
+  }
+
+  /// Get definitions of a word grouped by source dictionary, in the order
+  /// the dictionaries first appear, see [group_by_source].
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  /// for (source, definitions) in api.get_definitions_by_source("word").unwrap() {
+  ///   println!("{}: {} definitions", source, definitions.len());
+  /// }
+  /// ```
+  pub fn get_definitions_by_source(
+    &self,
+    word: &str,
+  ) -> Result<Vec<(String, Vec<Definition>)>, WordnikError> {
+    Ok(group_by_source(self.get_definitions(word)?))
+  }
+
+  /// Get the first definition of a word, or `None` if it has none.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// if let Some(def) = api.get_definition("word").unwrap() {
+  ///   println!("{}", def.definition.unwrap_or_default());
+  /// }
+  /// ```
+  pub fn get_definition(
+    &self,
+    word: &str,
+  ) -> Result<Option<Definition>, WordnikError> {
+    Ok(self.get_definitions(word)?.into_iter().next())
+  }
+
+  /// Get the most frequent part of speech among the definitions of a
+  /// word, e.g. for tagging, or `None` if none has one. On a tie, the
+  /// one the API lists first wins.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::Wordnik;
+  /// let def = |part_of_speech: &str| {
+  ///   json!({
+  ///     "word": "run",
+  ///     "text": "A definition.",
+  ///     "partOfSpeech": part_of_speech,
+  ///     "attributionText": "",
+  ///     "sourceDictionary": "",
+  ///     "attributionUrl": "",
+  ///     "wordnikUrl": ""
+  ///   })
+  /// };
+  /// let body = json!([def("noun"), def(""), def("verb"), def("verb"), def("noun")]);
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   move |_: &str, _: &HeaderMap| Ok(body.clone()),
+  /// );
+  /// assert_eq!(api.primary_part_of_speech("run").unwrap().as_deref(), Some("noun"));
+  /// ```
+  pub fn primary_part_of_speech(
+    &self,
+    word: &str,
+  ) -> Result<Option<String>, WordnikError> {
+    Ok(call::primary_part_of_speech(self.get_definitions(word)?))
+  }
+
+  /// Check whether the API knows a word, with a lookup of a single
+  /// definition.
+  ///
+  /// A 404 response means `false` rather than an error.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// assert!(api.word_exists("word").unwrap());
+  /// ```
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::{json, Value};
+  /// # use wordnik::{Requester, Wordnik, WordnikError};
+  /// struct Dictionary;
+  ///
+  /// impl Requester for Dictionary {
+  ///   fn request(&self, url: &str, _: &HeaderMap) -> Result<Value, WordnikError> {
+  ///     assert!(url.ends_with("/definitions?limit=1"));
+  ///     if url.contains("/word/") {
+  ///       Ok(json!([{ "text": "A unit of language." }]))
+  ///     } else {
+  ///       Err(WordnikError::NotFound)
+  ///     }
+  ///   }
+  /// }
+  ///
+  /// # let api = Wordnik::with_requester(
+  /// #   "KEY".to_string(),
+  /// #   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// #   Dictionary,
+  /// # );
+  /// assert!(api.word_exists("word").unwrap());
+  /// assert!(!api.word_exists("wrod").unwrap());
+  /// ```
+  pub fn word_exists(&self, word: &str) -> Result<bool, WordnikError> {
+    match self.execute(call::word_exists(word)) {
+      Err(WordnikError::NotFound) => Ok(false),
+      res => res,
+    }
+  }
+
+  /// Get the headword the API files a word under, e.g. "goose" for
+  /// "geese", with a single one-entry definitions request. A word the API
+  /// can't resolve is [NotFound](WordnikError::NotFound), rather than
+  /// given back as is.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert!(url.ends_with("/definitions?useCanonical=true&limit=1"));
+  ///     match url.contains("/geese/") {
+  ///       true => Ok(json!([{ "word": "goose", "text": "A large bird." }])),
+  ///       false => Ok(json!([])),
+  ///     }
+  ///   },
+  /// );
+  /// assert_eq!(api.canonicalize("geese").unwrap(), "goose");
+  /// assert!(matches!(api.canonicalize("gheese"), Err(WordnikError::NotFound)));
+  /// ```
+  pub fn canonicalize(&self, word: &str) -> Result<String, WordnikError> {
+    self.execute(call::canonical(word))
+  }
+
+  /// Get definitions of a word.
+  ///
+  /// # Arguments
+  ///
+  /// * `word`      - The word whose meaning you want.
+  /// * `textwidth` - The column at which to wrap the text.
+  ///   Pass 0 if you don't want to wrap at all.
+  /// * `dicts`     - The dictionaries you want to use.
+  ///   Pass an empty vector if you want to use all.
+  ///   The available dictionaries are:
+  ///   - "ahd-5": American Heritage Dictionary
+  ///   - "century": The Century Dictionary
+  ///   - "gcide": GNU version of the Collaborative International Dictionary of English
+  ///   - "wiktionary": Wiktionary
+  ///   - "wordnet": WordNet 3.0 Copyright 2006 by Princeton University
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let v = api
+  ///   .get_definitions_pretty("word", 80, vec!["ahd-5", "wordnet"])
+  ///   .unwrap();
+  /// println!("{}", v);
+  /// ```
+  ///
+  /// The dictionaries are listed in the order they first appear in the
+  /// response, and each one's definitions in the order the API ranked
+  /// them, so the output is the same every time.
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::Wordnik;
+  /// let def = |text: &str, attribution: &str| {
+  ///   json!({
+  ///     "word": "run",
+  ///     "text": text,
+  ///     "attributionText": attribution,
+  ///     "sourceDictionary": attribution.trim_start_matches("from "),
+  ///     "attributionUrl": "",
+  ///     "wordnikUrl": ""
+  ///   })
+  /// };
+  /// let body = json!([
+  ///   def("To move swiftly.", "from Wordnet"),
+  ///   def("To flee.", "from GCIDE"),
+  ///   def("To compete in a race.", "from Wordnet"),
+  ///   def("A pace faster than a walk.", "from Century"),
+  /// ]);
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   move |_: &str, _: &HeaderMap| Ok(body.clone()),
+  /// );
+  /// let first = api.get_definitions_pretty("run", 0, vec![]).unwrap();
+  /// assert_eq!(
+  ///   first,
+  ///   "from Wordnet\n  * To move swiftly.\n  * To compete in a race.\n\n\
+  ///    from GCIDE\n  * To flee.\n\n\
+  ///    from Century\n  * A pace faster than a walk.\n\n",
+  /// );
+  /// for _ in 0..10 {
+  ///   assert_eq!(api.get_definitions_pretty("run", 0, vec![]).unwrap(), first);
+  /// }
+  /// ```
+  ///
+  /// Wrapped lines are indented to align with the start of the text.
+  /// Widths are counted in columns, so non-ASCII text wraps correctly.
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::Wordnik;
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |_: &str, _: &HeaderMap| {
+  ///     Ok(json!([{
+  ///       "word": "café",
+  ///       "text": "A small restaurant, café or bistro, selling light meals and drinks.",
+  ///       "partOfSpeech": "noun",
+  ///       "attributionText": "from Wiktionary",
+  ///       "sourceDictionary": "wiktionary",
+  ///       "attributionUrl": "",
+  ///       "wordnikUrl": ""
+  ///     }]))
+  ///   },
+  /// );
+  /// let pretty = api.get_definitions_pretty("café", 30, vec![]).unwrap();
+  /// let expected = [
+  ///   "from Wiktionary",
+  ///   "  * noun A small restaurant,",
+  ///   "         café or bistro,",
+  ///   "         selling light meals",
+  ///   "         and drinks.",
+  ///   "",
+  ///   "",
+  /// ];
+  /// assert_eq!(pretty, expected.join("\n"));
+  /// ```
+  pub fn get_definitions_pretty(
+    &self,
+    word: &str,
+    textwidth: usize,
+    dicts: Vec<&str>,
+  ) -> Result<String, WordnikError> {
+    let opts = PrettyOptions::new().textwidth(textwidth).sources(&dicts);
+    self.get_definitions_pretty_with(word, &opts)
+  }
+
+  /// Get definitions of a word as a pretty string, formatted as `opts`
+  /// says. The options for the other sections of
+  /// [get_full_report_pretty_with](Self::get_full_report_pretty_with) are
+  /// ignored.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{PrettyOptions, Wordnik};
+  ///
+  /// # let def = |text: &str, source: &str, attribution: &str| {
+  /// #   json!({
+  /// #     "word": "run",
+  /// #     "text": text,
+  /// #     "partOfSpeech": "verb",
+  /// #     "attributionText": attribution,
+  /// #     "sourceDictionary": source,
+  /// #     "attributionUrl": "",
+  /// #     "wordnikUrl": ""
+  /// #   })
+  /// # };
+  /// # let body = json!([
+  /// #   def("To move swiftly.", "ahd-5", "from AHD"),
+  /// #   def("To flee.", "ahd-5", "from AHD"),
+  /// #   def("To compete.", "ahd-5", "from AHD"),
+  /// #   def("To go by running.", "gcide", "from GCIDE"),
+  /// #   def("To operate.", "wordnet", "from Wordnet"),
+  /// # ]);
+  /// # let api = Wordnik::with_requester(
+  /// #   "YOUR_API_KEY".to_string(),
+  /// #   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// #   move |_: &str, _: &HeaderMap| Ok(body.clone()),
+  /// # );
+  /// let opts = PrettyOptions::new().max_per_source(2).numbered(true);
+  /// let expected = [
+  ///   "from AHD",
+  ///   "  1. verb To move swiftly.",
+  ///   "  2. verb To flee.",
+  ///   "",
+  ///   "from GCIDE",
+  ///   "  1. verb To go by running.",
+  ///   "",
+  ///   "from Wordnet",
+  ///   "  1. verb To operate.",
+  ///   "",
+  ///   "",
+  /// ];
+  /// assert_eq!(api.get_definitions_pretty_with("run", &opts).unwrap(), expected.join("\n"));
+  ///
+  /// let opts = PrettyOptions::new()
+  ///   .exclude_sources(&["gcide"])
+  ///   .max_definitions(2)
+  ///   .attributions(false);
+  /// let expected = ["  * verb To move swiftly.", "  * verb To flee.", "", ""];
+  /// assert_eq!(api.get_definitions_pretty_with("run", &opts).unwrap(), expected.join("\n"));
+  ///
+  /// let opts = PrettyOptions::new().sources(&["wordnet", "gcide"]);
+  /// let expected = [
+  ///   "from GCIDE",
+  ///   "  * verb To go by running.",
+  ///   "",
+  ///   "from Wordnet",
+  ///   "  * verb To operate.",
+  ///   "",
+  ///   "",
+  /// ];
+  /// assert_eq!(api.get_definitions_pretty_with("run", &opts).unwrap(), expected.join("\n"));
+  /// assert_eq!(
+  ///   api.get_definitions_pretty("run", 0, vec!["wordnet", "gcide"]).unwrap(),
+  ///   expected.join("\n"),
+  /// );
+  /// ```
+  pub fn get_definitions_pretty_with(
+    &self,
+    word: &str,
+    opts: &PrettyOptions,
+  ) -> Result<String, WordnikError> {
+    let definitions = self.get_definitions(word)?;
+    Ok(format_definitions(&definitions, opts))
+  }
+
+  /// Get definitions of a word as Markdown, e.g. for notes or issue
+  /// comments: a heading for the word, a subheading per dictionary and a
+  /// numbered list of its definitions, with the part of speech in bold.
+  ///
+  /// Italic markup in the text is kept as `*italics*`, other markup is
+  /// dropped, and characters Markdown would interpret are escaped.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::Wordnik;
+  /// let def = |text: &str, part_of_speech: &str, attribution: &str| {
+  ///   json!({
+  ///     "word": "run",
+  ///     "text": text,
+  ///     "partOfSpeech": part_of_speech,
+  ///     "attributionText": attribution,
+  ///     "sourceDictionary": attribution.trim_start_matches("from "),
+  ///     "attributionUrl": "",
+  ///     "wordnikUrl": ""
+  ///   })
+  /// };
+  /// let body = json!([
+  ///   def("To move <em>swiftly</em> on foot.", "verb", "from Wordnet"),
+  ///   def("To flee, as in <xref>run_away</xref>.", "verb", "from GCIDE"),
+  ///   def("A pace faster than a walk; [a *jog*].", "noun", "from Wordnet"),
+  /// ]);
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   move |_: &str, _: &HeaderMap| Ok(body.clone()),
+  /// );
+  /// let expected = [
+  ///   "# run",
+  ///   "",
+  ///   "### from Wordnet",
+  ///   "",
+  ///   "1. **verb** To move *swiftly* on foot.",
+  ///   "2. **noun** A pace faster than a walk; \\[a \\*jog\\*\\].",
+  ///   "",
+  ///   "### from GCIDE",
+  ///   "",
+  ///   "1. **verb** To flee, as in run\\_away.",
+  ///   "",
+  /// ];
+  /// assert_eq!(api.get_definitions_markdown("run").unwrap(), expected.join("\n"));
+  /// ```
+  pub fn get_definitions_markdown(
+    &self,
+    word: &str,
+  ) -> Result<String, WordnikError> {
+    let definitions = self.get_definitions(word)?;
+    Ok(format_definitions_markdown(word, &definitions))
+  }
+
+  /// Get pronunciations of a word, sorted by
+  /// [seq](Pronunciation::seq), which the API doesn't always do.
+  ///
+  /// For IPA notation only, see [get_ipa](Self::get_ipa).
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let v = api.get_pronunciations("word").unwrap();
+  /// println!("{:#?}", v);
+  /// ```
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::{Wordnik};
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert_eq!(
+  ///       url,
+  ///       "https://api.wordnik.com/v4/word.json/word/pronunciations",
+  ///     );
+  ///     Ok(json!([
+  ///       { "seq": 1, "raw": "W1", "rawType": "ahd-5" },
+  ///       { "seq": 0, "raw": "/wɜːd/", "rawType": "IPA" },
+  ///     ]))
+  ///   },
+  /// );
+  /// let v = api.get_pronunciations("word").unwrap();
+  /// assert_eq!(v[0].raw, "/wɜːd/");
+  /// assert_eq!(v[0].raw_type, "IPA");
+  /// assert_eq!(v[1].seq, 1);
+  /// ```
+  pub fn get_pronunciations(
+    &self,
+    word: &str,
+  ) -> Result<Vec<Pronunciation>, WordnikError> {
+    self.execute(call::pronunciations(word))
+  }
+
+  /// Get IPA pronunciations of a word.
+  ///
+  /// Returns an empty vector if none of the pronunciations are in IPA.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// for ipa in api.get_ipa("word").unwrap() {
+  ///   println!("{}", ipa);
+  /// }
+  /// ```
+  pub fn get_ipa(&self, word: &str) -> Result<Vec<String>, WordnikError> {
+    Ok(call::ipa_only(self.get_pronunciations(word)?))
+  }
+
+  /// Get recorded pronunciations of a word.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::Wordnik;
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert_eq!(url, "https://api.wordnik.com/v4/word.json/word/audio");
+  ///     Ok(json!([{
+  ///       "id": 1,
+  ///       "word": "word",
+  ///       "fileUrl": "https://api.wordnik.com/v4/audioFile.mp3/abc",
+  ///       "audioType": "pronunciation",
+  ///       "duration": 1.2,
+  ///       "createdBy": "ahd",
+  ///       "createdAt": "2009-03-15T15:30:37.000+0000"
+  ///     }]))
+  ///   },
+  /// );
+  /// let mut audio = api.get_audio("word").unwrap();
+  /// // Most recently added first.
+  /// audio.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+  /// assert_eq!(audio[0].duration, 1.2);
+  /// assert!(audio[0].created_at.is_some());
+  /// ```
+  pub fn get_audio(&self, word: &str) -> Result<Vec<Audio>, WordnikError> {
+    self.execute(call::audio(word))
+  }
+
+  /// Get usage examples of a word.
+  ///
+  /// To restrict the examples, see [get_examples_with](Self::get_examples_with).
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let v = api.get_examples("word").unwrap();
+  /// println!("{:#?}", v);
+  /// ```
+  ///
+  /// A word without examples gets an empty vector, whether the response
+  /// has an empty list, a `null` or no list at all:
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::Wordnik;
+  /// for body in [json!({ "examples": [] }), json!({ "examples": null }), json!({})] {
+  ///   let api = Wordnik::with_requester(
+  ///     "YOUR_API_KEY".to_string(),
+  ///     "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///     move |_: &str, _: &HeaderMap| Ok(body.clone()),
+  ///   );
+  ///   assert!(api.get_examples("obscure").unwrap().is_empty());
+  ///   assert_eq!(api.get_examples_paged("obscure", &Default::default()).unwrap().total, 0);
+  /// }
+  /// ```
+  pub fn get_examples(&self, word: &str) -> Result<Vec<Example>, WordnikError> {
+    self.get_examples_with(word, &ExamplesQuery::default())
+  }
+
+  /// Get usage examples of a word, restricted by `query`.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::{ExamplesQuery, Wordnik};
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let query = ExamplesQuery::new().min_year(2000);
+  /// for example in api.get_examples_with("word", &query).unwrap() {
+  ///   println!("{:?}: {}", example.year, example.text);
+  /// }
+  /// ```
+  pub fn get_examples_with(
+    &self,
+    word: &str,
+    query: &ExamplesQuery,
+  ) -> Result<Vec<Example>, WordnikError> {
+    Ok(self.get_examples_paged(word, query)?.items)
+  }
+
+  /// Get usage examples of a word along with the total number available.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::{ExamplesQuery, Wordnik};
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let query = ExamplesQuery::new().limit(10);
+  /// let page = api.get_examples_paged("word", &query).unwrap();
+  /// println!("showing {} of {}", page.items.len(), page.total);
+  /// ```
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::{ExamplesQuery, Wordnik};
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert_eq!(
+  ///       url,
+  ///       "https://api.wordnik.com/v4/word.json/word/examples?minYear=2000&maxYear=2010&skip=5&limit=1",
+  ///     );
+  ///     Ok(json!({
+  ///       "totalResults": 42,
+  ///       "examples": [{ "text": "A word to the wise.", "year": 2004 }],
+  ///     }))
+  ///   },
+  /// );
+  /// let query = ExamplesQuery::new()
+  ///   .min_year(2000)
+  ///   .max_year(2010)
+  ///   .skip(5)
+  ///   .limit(1);
+  /// let page = api.get_examples_paged("word", &query).unwrap();
+  /// assert_eq!(page.total, 42);
+  /// assert_eq!(page.items[0].year, Some(2004));
+  /// ```
+  pub fn get_examples_paged(
+    &self,
+    word: &str,
+    query: &ExamplesQuery,
+  ) -> Result<Paged<Example>, WordnikError> {
+    self.execute(call::examples(word, query))
+  }
+
+  /// Get the example the API rates best, in one small request.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert!(url.ends_with("/word/topExample"));
+  ///     Ok(json!({ "word": "word", "text": "Say the word.", "rating": 9.5 }))
+  ///   },
+  /// );
+  /// assert_eq!(api.get_top_example("word").unwrap().text, "Say the word.");
+  /// ```
+  pub fn get_top_example(&self, word: &str) -> Result<Example, WordnikError> {
+    self.execute(call::top_example(word))
+  }
+
+  /// Get the text of the top example of a word, with HTML entities
+  /// decoded, or `None` if it has none. Like
+  /// [get_top_example](Self::get_top_example), it takes one small
+  /// request.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| match url.contains("/word/") {
+  ///     true => Ok(json!({ "text": "&quot;Say the word,&quot; she said." })),
+  ///     false => Err(WordnikError::NotFound),
+  ///   },
+  /// );
+  /// let example = api.first_example("word").unwrap();
+  /// assert_eq!(example.as_deref(), Some("\"Say the word,\" she said."));
+  /// assert_eq!(api.first_example("xyzzy").unwrap(), None);
+  /// ```
+  pub fn first_example(
+    &self,
+    word: &str,
+  ) -> Result<Option<String>, WordnikError> {
+    match self.get_top_example(word) {
+      Ok(example) => Ok(call::example_text(example)),
+      Err(WordnikError::NotFound) => Ok(None),
+      Err(e) => Err(e),
+    }
+  }
+
+  /// Iterate over all usage examples of a word, fetching them a page at a
+  /// time as the iteration goes.
+  ///
+  /// An error ends the iteration after being yielded.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// for example in api.examples_iter("run") {
+  ///   println!("{}", example.unwrap().text);
+  /// }
+  /// ```
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::Wordnik;
+  /// // 120 examples, served 50 at a time.
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     let skip: usize = url.split("skip=").nth(1).unwrap()
+  ///       .split('&').next().unwrap().parse().unwrap();
+  ///     let examples: Vec<_> = (skip..120.min(skip + 50))
+  ///       .map(|i| json!({ "text": i.to_string() }))
+  ///       .collect();
+  ///     Ok(json!({ "examples": examples }))
+  ///   },
+  /// );
+  /// let texts: Vec<String> = api
+  ///   .examples_iter("run")
+  ///   .map(|example| example.unwrap().text)
+  ///   .collect();
+  /// assert_eq!(texts.len(), 120);
+  /// assert_eq!(texts[119], "119");
+  /// ```
+  pub fn examples_iter(
+    &self,
+    word: &str,
+  ) -> impl Iterator<Item = Result<Example, WordnikError>> + '_ {
+    self.examples_iter_with(word, &ExamplesQuery::default())
+  }
+
+  /// Iterate over the usage examples of a word matching `query`, fetching
+  /// them [page_size](ExamplesQuery::page_size) at a time. The query's
+  /// [limit](ExamplesQuery::limit) bounds the examples fetched in total,
+  /// to keep a long iteration from using up the API quota.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use std::sync::{Arc, Mutex};
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{ExamplesQuery, Wordnik};
+  ///
+  /// # let requests = Arc::new(Mutex::new(Vec::new()));
+  /// # let log = requests.clone();
+  /// // 120 examples.
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   move |url: &str, _: &HeaderMap| {
+  ///     let param = |name: &str| -> usize {
+  ///       let value = url.split(&format!("{}=", name)).nth(1).unwrap();
+  ///       value.split('&').next().unwrap().parse().unwrap()
+  ///     };
+  ///     let (skip, limit) = (param("skip"), param("limit"));
+  /// #   log.lock().unwrap().push((skip, limit));
+  ///     let examples: Vec<_> = (skip..120.min(skip + limit))
+  ///       .map(|i| json!({ "text": i.to_string() }))
+  ///       .collect();
+  ///     Ok(json!({ "examples": examples }))
+  ///   },
+  /// );
+  /// let query = ExamplesQuery::new().page_size(20).limit(50);
+  /// let texts: Vec<String> = api
+  ///   .examples_iter_with("run", &query)
+  ///   .map(|example| example.unwrap().text)
+  ///   .collect();
+  /// assert_eq!(texts.len(), 50);
+  /// assert_eq!(texts[49], "49");
+  /// # assert_eq!(*requests.lock().unwrap(), [(0, 20), (20, 20), (40, 10)]);
+  /// ```
+  pub fn examples_iter_with(
+    &self,
+    word: &str,
+    query: &ExamplesQuery,
+  ) -> impl Iterator<Item = Result<Example, WordnikError>> + '_ {
+    ExamplesIter::new(self, word, query)
+  }
+
+  /// Get words related to a word, grouped by relationship type.
+  ///
+  /// To restrict the relationship types, see
+  /// [get_related_words_with](Self::get_related_words_with).
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let v = api.get_related_words("word").unwrap();
+  /// println!("{:#?}", v);
+  /// ```
+  pub fn get_related_words(
+    &self,
+    word: &str,
+  ) -> Result<Vec<RelatedWords>, WordnikError> {
+    self.get_related_words_with(word, &RelatedWordsQuery::default())
+  }
+
+  /// Get words related to a word, restricted by `query`.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::{RelatedWordsQuery, RelationshipType, Wordnik};
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let query = RelatedWordsQuery::new()
+  ///   .relationship_types(&[RelationshipType::Synonym])
+  ///   .limit_per_relationship_type(10);
+  /// for group in api.get_related_words_with("word", &query).unwrap() {
+  ///   println!("{}: {:?}", group.relationship_type, group.words);
+  /// }
+  /// ```
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::{RelatedWordsQuery, RelationshipType, Wordnik};
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert_eq!(
+  ///       url,
+  ///       "https://api.wordnik.com/v4/word.json/word/relatedWords?relationshipTypes=synonym,antonym&limitPerRelationshipType=2",
+  ///     );
+  ///     Ok(json!([{ "relationshipType": "synonym", "words": ["term", "vocable"] }]))
+  ///   },
+  /// );
+  /// let query = RelatedWordsQuery::new()
+  ///   .relationship_types(&[RelationshipType::Synonym, RelationshipType::Antonym])
+  ///   .limit_per_relationship_type(2);
+  /// let v = api.get_related_words_with("word", &query).unwrap();
+  /// assert_eq!(v[0].relationship_type, RelationshipType::Synonym);
+  /// assert_eq!(v[0].words, ["term", "vocable"]);
+  /// ```
+  pub fn get_related_words_with(
+    &self,
+    word: &str,
+    query: &RelatedWordsQuery,
+  ) -> Result<Vec<RelatedWords>, WordnikError> {
+    self.execute(call::related_words(word, query))
+  }
+
+  /// Get the synonyms and the antonyms of a word, in that order.
+  ///
+  /// Both are empty if the word has neither, including when the API
+  /// answers 404.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let (synonyms, antonyms) = api.get_synonyms_antonyms("hot").unwrap();
+  /// println!("{:?} / {:?}", synonyms, antonyms);
+  /// ```
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::{Wordnik, WordnikError};
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert!(url.ends_with("/relatedWords?relationshipTypes=synonym,antonym"));
+  ///     if url.contains("/hot/") {
+  ///       Ok(json!([
+  ///         { "relationshipType": "antonym", "words": ["cold"] },
+  ///         { "relationshipType": "synonym", "words": ["warm", "heated"] },
+  ///       ]))
+  ///     } else {
+  ///       Err(WordnikError::NotFound)
+  ///     }
+  ///   },
+  /// );
+  /// let (synonyms, antonyms) = api.get_synonyms_antonyms("hot").unwrap();
+  /// assert_eq!(synonyms, ["warm", "heated"]);
+  /// assert_eq!(antonyms, ["cold"]);
+  ///
+  /// let (synonyms, antonyms) = api.get_synonyms_antonyms("the").unwrap();
+  /// assert!(synonyms.is_empty() && antonyms.is_empty());
+  /// ```
+  pub fn get_synonyms_antonyms(
+    &self,
+    word: &str,
+  ) -> Result<(Vec<String>, Vec<String>), WordnikError> {
+    match self.execute(call::synonyms_antonyms(word)) {
+      Err(WordnikError::NotFound) => Ok((Vec::new(), Vec::new())),
+      res => res,
+    }
+  }
+
+  /// Get the synonyms of a word, without repeats, or an empty list if it
+  /// has none.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| match url.split('/').nth(5) {
+  ///     Some("hot") => Ok(json!([
+  ///       { "relationshipType": "synonym", "words": ["warm", "heated"] },
+  ///       { "relationshipType": "synonym", "words": ["warm", "fiery"] },
+  ///     ])),
+  ///     Some("cold") => Ok(json!([])),
+  ///     _ => Err(WordnikError::NotFound),
+  ///   },
+  /// );
+  /// assert_eq!(api.get_synonyms("hot").unwrap(), ["warm", "heated", "fiery"]);
+  /// assert!(api.get_synonyms("cold").unwrap().is_empty());
+  /// assert!(api.get_synonyms("the").unwrap().is_empty());
+  /// ```
+  pub fn get_synonyms(&self, word: &str) -> Result<Vec<String>, WordnikError> {
+    self.related_of(word, RelationshipType::Synonym, None)
+  }
+
+  /// Get the antonyms of a word, like [get_synonyms](Self::get_synonyms).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert!(url.ends_with("/relatedWords?relationshipTypes=antonym"));
+  ///     Ok(match url.contains("/hot/") {
+  ///       true => json!([{ "relationshipType": "antonym", "words": ["cold"] }]),
+  ///       false => json!([]),
+  ///     })
+  ///   },
+  /// );
+  /// assert_eq!(api.get_antonyms("hot").unwrap(), ["cold"]);
+  /// assert!(api.get_antonyms("blue").unwrap().is_empty());
+  /// ```
+  pub fn get_antonyms(&self, word: &str) -> Result<Vec<String>, WordnikError> {
+    self.related_of(word, RelationshipType::Antonym, None)
+  }
+
+  /// Get the hypernyms of a word, the more general terms it is a kind of,
+  /// e.g. "canine" for "dog", like [get_synonyms](Self::get_synonyms).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |_: &str, _: &HeaderMap| {
+  ///     Ok(json!([
+  ///       { "relationshipType": "synonym", "words": ["hound"] },
+  ///       { "relationshipType": "hypernym", "words": ["canine", "domestic animal"] },
+  ///       { "relationshipType": "hyponym", "words": ["puppy", "poodle"] },
+  ///     ]))
+  ///   },
+  /// );
+  /// assert_eq!(api.get_hypernyms("dog").unwrap(), ["canine", "domestic animal"]);
+  /// assert_eq!(api.get_hyponyms("dog").unwrap(), ["puppy", "poodle"]);
+  /// ```
+  pub fn get_hypernyms(&self, word: &str) -> Result<Vec<String>, WordnikError> {
+    self.related_of(word, RelationshipType::Hypernym, None)
+  }
+
+  /// Get the hyponyms of a word, the more specific terms that are kinds
+  /// of it, e.g. "poodle" for "dog", like
+  /// [get_synonyms](Self::get_synonyms).
+  pub fn get_hyponyms(&self, word: &str) -> Result<Vec<String>, WordnikError> {
+    self.related_of(word, RelationshipType::Hyponym, None)
+  }
+
+  /// Get the single words that rhyme with a word, or an empty list if
+  /// the API has no rhymes for it. Up to [RHYME_LIMIT] rhymes are
+  /// requested; phrases and the word itself are left out.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert!(url.ends_with(
+  ///       "/relatedWords?relationshipTypes=rhyme&limitPerRelationshipType=1000",
+  ///     ));
+  ///     match url.contains("/night/") {
+  ///       true => Ok(json!([{
+  ///         "relationshipType": "rhyme",
+  ///         "words": ["light", "Night", "fight", "first sight", "bright"],
+  ///       }])),
+  ///       false => Err(WordnikError::NotFound),
+  ///     }
+  ///   },
+  /// );
+  /// assert_eq!(api.get_rhymes("night").unwrap(), ["light", "fight", "bright"]);
+  /// assert!(api.get_rhymes("orange").unwrap().is_empty());
+  /// ```
+  pub fn get_rhymes(&self, word: &str) -> Result<Vec<String>, WordnikError> {
+    let rhymes =
+      self.related_of(word, RelationshipType::Rhyme, Some(RHYME_LIMIT))?;
+    Ok(call::single_words_except(rhymes, word))
+  }
+
+  /// The words related to `word` by `relationship_type`, or none if the
+  /// API doesn't know the word.
+  fn related_of(
+    &self,
+    word: &str,
+    relationship_type: RelationshipType,
+    limit: Option<u32>,
+  ) -> Result<Vec<String>, WordnikError> {
+    match self.execute(call::related_of(word, relationship_type, limit)) {
+      Err(WordnikError::NotFound) => Ok(Vec::new()),
+      res => res,
+    }
+  }
+
+  /// Get definitions, examples and pronunciations of a word in one go.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let details = api.get_word_details("word").unwrap();
+  /// println!("{:#?}", details.definitions);
+  /// ```
+  pub fn get_word_details(
+    &self,
+    word: &str,
+  ) -> Result<WordDetails, WordnikError> {
+    Ok(WordDetails {
+      definitions: self.get_definitions(word)?,
+      examples: self.get_examples(word)?,
+      pronunciations: self.get_pronunciations(word)?,
+    })
+  }
+
+  /// Get a report of a word from every endpoint with a section in
+  /// [WordReport]: definitions, etymologies, pronunciations, hyphenation,
+  /// the top example, related words, the Scrabble score, the frequency
+  /// and audio.
+  ///
+  /// The requests are sent one after the other. A failed one leaves its
+  /// section `None` and adds its error to [WordReport::errors]; only an
+  /// invalid word fails the whole report.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{Operation, Wordnik, WordnikError};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     let operation = url.rsplit('/').next().unwrap();
+  ///     Ok(match operation {
+  ///       "definitions" => json!([{
+  ///         "word": "word",
+  ///         "text": "A unit of language.",
+  ///         "partOfSpeech": "noun",
+  ///         "attributionText": "from Wordnet",
+  ///         "sourceDictionary": "wordnet",
+  ///         "attributionUrl": "",
+  ///         "wordnikUrl": ""
+  ///       }]),
+  ///       "etymologies" => json!(["<ety>[<ets>Old English</ets> <er>word</er>.]</ety>"]),
+  ///       "pronunciations" => json!([{ "raw": "/wɜːd/", "rawType": "IPA" }]),
+  ///       "hyphenation" => json!([{ "text": "word", "seq": 0 }]),
+  ///       "topExample" => json!({ "text": "Say the word." }),
+  ///       "relatedWords" => json!([
+  ///         { "relationshipType": "synonym", "words": ["term", "vocable"] },
+  ///       ]),
+  ///       "scrabbleScore" => json!({ "value": 8 }),
+  ///       _ => return Err(WordnikError::NotFound),
+  ///     })
+  ///   },
+  /// );
+  /// let report = api.get_word_report("word").unwrap();
+  /// assert_eq!(report.scrabble_score.unwrap().value, 8);
+  /// assert!(report.frequency.is_none());
+  /// let failed: Vec<&Operation> = report.errors.iter().map(|(op, _)| op).collect();
+  /// assert_eq!(failed, [&Operation::Frequency, &Operation::Audio]);
+  ///
+  /// let expected = [
+  ///   "word",
+  ///   "word",
+  ///   "/wɜːd/",
+  ///   "",
+  ///   "from Wordnet",
+  ///   "  * noun A unit of language.",
+  ///   "",
+  ///   "Etymology",
+  ///   "  * Old English word.",
+  ///   "",
+  ///   "Example",
+  ///   "  * Say the word.",
+  ///   "",
+  ///   "Related words",
+  ///   "  * synonym: term, vocable",
+  ///   "",
+  ///   "Scrabble score: 8",
+  ///   "",
+  ///   "Errors",
+  ///   "  * frequency: word not found",
+  ///   "  * audio: word not found",
+  ///   "",
+  ///   "",
+  /// ];
+  /// assert_eq!(report.pretty(), expected.join("\n"));
+  /// ```
+  pub fn get_word_report(
+    &self,
+    word: &str,
+  ) -> Result<WordReport, WordnikError> {
+    self.get_word_report_with(word, &report::REPORT_OPERATIONS)
+  }
+
+  /// Get a report of a word with only the sections of `operations`, e.g.
+  /// `&[Operation::Definitions, Operation::TopExample]`. Operations
+  /// without a section in [WordReport] are ignored.
+  pub fn get_word_report_with(
+    &self,
+    word: &str,
+    operations: &[Operation],
+  ) -> Result<WordReport, WordnikError> {
+    call::encode_segment(word)?;
+    let wants = |operation: Operation| {
+      operations.contains(&operation).then_some(operation)
+    };
+    let mut report = WordReport::new(word);
+    for operation in report::REPORT_OPERATIONS.into_iter().filter_map(wants) {
+      match operation {
+        Operation::Definitions => {
+          let res = self.get_definitions(word);
+          report.definitions = report.section(operation, Some(res));
+        }
+        Operation::Etymologies => {
+          let res = self.get_etymologies(word);
+          report.etymologies = report.section(operation, Some(res));
+        }
+        Operation::Pronunciations => {
+          let res = self.get_pronunciations(word);
+          report.pronunciations = report.section(operation, Some(res));
+        }
+        Operation::Hyphenation => {
+          let res = self.get_hyphenation(word);
+          report.hyphenation = report.section(operation, Some(res));
+        }
+        Operation::TopExample => {
+          let res = self.get_top_example(word);
+          report.top_example = report.section(operation, Some(res));
+        }
+        Operation::RelatedWords => {
+          let res = self.get_related_words(word);
+          report.related_words = report.section(operation, Some(res));
+        }
+        Operation::ScrabbleScore => {
+          let res = self.get_scrabble_score(word);
+          report.scrabble_score = report.section(operation, Some(res));
+        }
+        Operation::Frequency => {
+          let res = self.get_frequency(word);
+          report.frequency = report.section(operation, Some(res));
+        }
+        Operation::Audio => {
+          let res = self.get_audio(word);
+          report.audio = report.section(operation, Some(res));
+        }
+        _ => {}
+      }
+    }
+    Ok(report)
+  }
+
+  /// Get the number of occurrences of a word, per year.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let v = api.get_frequency("word").unwrap();
+  /// println!("{}", v.total_count);
+  /// ```
+  pub fn get_frequency(&self, word: &str) -> Result<Frequency, WordnikError> {
+    self.get_frequency_with(word, &FrequencyQuery::default())
+  }
+
+  /// Get the number of occurrences of a word, restricted by `query`.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::{FrequencyQuery, Wordnik};
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let query = FrequencyQuery::new().start_year(2000);
+  /// for year in api.get_frequency_with("word", &query).unwrap().frequency {
+  ///   println!("{}: {}", year.year, year.count);
+  /// }
+  /// ```
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::{FrequencyQuery, Wordnik};
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert_eq!(
+  ///       url,
+  ///       "https://api.wordnik.com/v4/word.json/word/frequency?startYear=2000&endYear=2001",
+  ///     );
+  ///     Ok(json!({
+  ///       "totalCount": 7,
+  ///       "frequency": [{ "year": "2000", "count": 3 }, { "year": "2001", "count": 4 }],
+  ///     }))
+  ///   },
+  /// );
+  /// let query = FrequencyQuery::new().start_year(2000).end_year(2001);
+  /// let v = api.get_frequency_with("word", &query).unwrap();
+  /// assert_eq!(v.total_count, 7);
+  /// assert_eq!(v.frequency[1].count, 4);
+  /// ```
+  pub fn get_frequency_with(
+    &self,
+    word: &str,
+    query: &FrequencyQuery,
+  ) -> Result<Frequency, WordnikError> {
+    self.execute(call::frequency(word, query))
+  }
+
+  /// Classify a word as common, uncommon or rare by its total count, with
+  /// the default [FrequencyThresholds].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{FrequencyBand, FrequencyThresholds, Wordnik};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     let total_count = match url.contains("/house/") {
+  ///       true => 250_000,
+  ///       false => 120,
+  ///     };
+  ///     Ok(json!({ "totalCount": total_count, "frequency": [] }))
+  ///   },
+  /// );
+  /// assert_eq!(api.frequency_band("house").unwrap(), FrequencyBand::Common);
+  /// assert_eq!(api.frequency_band("quincunx").unwrap(), FrequencyBand::Rare);
+  ///
+  /// let thresholds = FrequencyThresholds { common: 1_000, uncommon: 100 };
+  /// let band = api.frequency_band_with("quincunx", &thresholds).unwrap();
+  /// assert_eq!(band, FrequencyBand::Uncommon);
+  /// ```
+  pub fn frequency_band(
+    &self,
+    word: &str,
+  ) -> Result<FrequencyBand, WordnikError> {
+    self.frequency_band_with(word, &FrequencyThresholds::default())
+  }
+
+  /// Classify a word by its total count with `thresholds`.
+  pub fn frequency_band_with(
+    &self,
+    word: &str,
+    thresholds: &FrequencyThresholds,
+  ) -> Result<FrequencyBand, WordnikError> {
+    Ok(thresholds.band(self.get_frequency(word)?.total_count))
+  }
+
+  /// Get the syllables of a word, sorted by [seq](Syllable::seq), which
+  /// the API doesn't always do.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let v = api.get_hyphenation("word").unwrap();
+  /// println!("{:#?}", v);
+  /// ```
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::{Wordnik};
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert_eq!(
+  ///       url,
+  ///       "https://api.wordnik.com/v4/word.json/dictionary/hyphenation",
+  ///     );
+  ///     Ok(json!([
+  ///       { "text": "tion", "seq": 1 },
+  ///       { "text": "dic", "seq": 0, "type": "stress" },
+  ///       { "text": "y", "seq": 3 },
+  ///       { "text": "ar", "seq": 2 },
+  ///     ]))
+  ///   },
+  /// );
+  /// let v = api.get_hyphenation("dictionary").unwrap();
+  /// assert_eq!(v.len(), 4);
+  /// let word: String = v.iter().map(|s| s.text.as_str()).collect();
+  /// assert_eq!(word, "dictionary");
+  /// assert_eq!(v[0].syllable_type.as_deref(), Some("stress"));
+  /// ```
+  pub fn get_hyphenation(
+    &self,
+    word: &str,
+  ) -> Result<Vec<Syllable>, WordnikError> {
+    self.execute(call::hyphenation(word))
+  }
+
+  /// Get the two-word phrases a word appears in.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::Wordnik;
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert_eq!(url, "https://api.wordnik.com/v4/word.json/word/phrases");
+  ///     Ok(json!([
+  ///       { "gram1": "word", "gram2": "processing", "count": 42, "mi": 9.5, "wlmi": 12.1 },
+  ///       { "gram1": "last", "gram2": "word", "count": 87, "mi": 6.2, "wlmi": 11.4 },
+  ///     ]))
+  ///   },
+  /// );
+  /// let phrases = api.get_phrases("word").unwrap();
+  /// assert_eq!(phrases.len(), 2);
+  /// assert_eq!(phrases[0].gram2, "processing");
+  /// assert_eq!(phrases[1].count, 87);
+  /// ```
+  pub fn get_phrases(&self, word: &str) -> Result<Vec<Phrase>, WordnikError> {
+    self.execute(call::phrases(word))
+  }
+
+  /// Get the etymologies of a word, parsed from their markup.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::Wordnik;
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert_eq!(url, "https://api.wordnik.com/v4/word.json/word/etymologies");
+  ///     Ok(json!([
+  ///       "<ety>[<ets>Middle English</ets>, from <ets>Old English</ets>.]</ety>"
+  ///     ]))
+  ///   },
+  /// );
+  /// let etymologies = api.get_etymologies("word").unwrap();
+  /// let languages: Vec<&str> = etymologies[0].languages().collect();
+  /// assert_eq!(languages, ["Middle English", "Old English"]);
+  /// ```
+  pub fn get_etymologies(
+    &self,
+    word: &str,
+  ) -> Result<Vec<Etymology>, WordnikError> {
+    self.execute(call::etymologies(word))
+  }
+
+  /// Get the Scrabble score of a word. For the points of each letter, see
+  /// [scrabble_breakdown].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{scrabble_breakdown, Wordnik};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert_eq!(
+  ///       url,
+  ///       "https://api.wordnik.com/v4/word.json/quiz/scrabbleScore",
+  ///     );
+  ///     Ok(json!({ "value": 22 }))
+  ///   },
+  /// );
+  /// let score = api.get_scrabble_score("quiz").unwrap();
+  /// let breakdown = scrabble_breakdown("quiz");
+  /// assert_eq!(breakdown.iter().map(|&(_, p)| p as i64).sum::<i64>(), score.value);
+  /// ```
+  pub fn get_scrabble_score(
+    &self,
+    word: &str,
+  ) -> Result<ScrabbleScore, WordnikError> {
+    self.execute(call::scrabble_score(word))
+  }
+
+  /// Whether a word is playable in Scrabble: the API scores it and the
+  /// score isn't 0. Words the API doesn't know are not playable, rather
+  /// than a [NotFound](WordnikError::NotFound) error.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{Wordnik, WordnikError};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| match url.contains("/quiz/") {
+  ///     true => Ok(json!({ "value": 22 })),
+  ///     false => Err(WordnikError::NotFound),
+  ///   },
+  /// );
+  /// assert!(api.is_valid_scrabble_word("quiz").unwrap());
+  /// assert!(!api.is_valid_scrabble_word("qwzx").unwrap());
+  /// ```
+  pub fn is_valid_scrabble_word(
+    &self,
+    word: &str,
+  ) -> Result<bool, WordnikError> {
+    match self.get_scrabble_score(word) {
+      Ok(score) => Ok(score.value > 0),
+      Err(WordnikError::NotFound) => Ok(false),
+      Err(e) => Err(e),
+    }
+  }
+
+  /// Get the response of any operation as JSON, as the API sent it.
+  ///
+  /// Use this for operations and fields the typed methods don't cover
+  /// yet. The word is encoded and errors are reported like everywhere
+  /// else.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::{Operation, Wordnik};
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let v = api.get_raw("word", Operation::ScrabbleScore).unwrap();
+  /// println!("{}", v["value"]);
+  /// ```
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// # use wordnik::{Operation, Wordnik};
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     assert_eq!(url, "https://api.wordnik.com/v4/word.json/ice%20cream/etymologies");
+  ///     Ok(json!(["<ety>From ice and cream.</ety>"]))
+  ///   },
+  /// );
+  /// let v = api.get_raw("ice cream", Operation::Etymologies).unwrap();
+  /// assert_eq!(v[0], "<ety>From ice and cream.</ety>");
+  /// ```
+  pub fn get_raw(
+    &self,
+    word: &str,
+    operation: Operation,
+  ) -> Result<Value, WordnikError> {
+    self.execute(call::raw(word, operation))
+  }
+
+  /// Get a pretty report of a word: its syllables, IPA pronunciations,
+  /// definitions and a couple of examples.
+  ///
+  /// To choose the sections, see
+  /// [get_full_report_pretty_with](Self::get_full_report_pretty_with).
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::Wordnik;
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// println!("{}", api.get_full_report_pretty("word").unwrap());
+  /// ```
+  pub fn get_full_report_pretty(
+    &self,
+    word: &str,
+  ) -> Result<String, WordnikError> {
+    self.get_full_report_pretty_with(word, &PrettyOptions::default())
+  }
+
+  /// Get a pretty report of a word, with the sections chosen by `opts`.
+  ///
+  /// Only the requests needed for the chosen sections are sent. A section
+  /// the API has nothing for, e.g. the examples of a rare word, is left
+  /// empty rather than failing the report.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// use wordnik::{PrettyOptions, Wordnik};
+  ///
+  /// let api = Wordnik::new(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// );
+  ///
+  /// let opts = PrettyOptions::new().examples(0).textwidth(80);
+  /// println!("{}", api.get_full_report_pretty_with("word", &opts).unwrap());
+  /// ```
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{PrettyOptions, Wordnik, WordnikError};
+  ///
+  /// let api = Wordnik::with_requester(
+  ///   "YOUR_API_KEY".to_string(),
+  ///   "https://api.wordnik.com/v4/word.json/".to_string(),
+  ///   |url: &str, _: &HeaderMap| {
+  ///     if url.contains("/definitions") {
+  ///       return Ok(json!([{
+  ///         "word": "quockerwodger",
+  ///         "text": "A wooden toy figure.",
+  ///         "partOfSpeech": "noun",
+  ///         "attributionText": "",
+  ///         "sourceDictionary": "",
+  ///         "attributionUrl": "",
+  ///         "wordnikUrl": ""
+  ///       }]));
+  ///     }
+  ///     Err(WordnikError::NotFound)
+  ///   },
+  /// );
+  /// let report = api.get_full_report_pretty("quockerwodger").unwrap();
+  /// assert!(report.contains("A wooden toy figure."));
+  /// ```
+  pub fn get_full_report_pretty_with(
+    &self,
+    word: &str,
+    opts: &PrettyOptions,
+  ) -> Result<String, WordnikError> {
+    let mut report = Report {
+      word,
+      hyphenation: Vec::new(),
+      ipa: Vec::new(),
+      definitions: Vec::new(),
+      examples: Vec::new(),
+    };
+    if opts.hyphenation {
+      report.hyphenation = call::or_empty(self.get_hyphenation(word))?;
+    }
+    if opts.pronunciation {
+      report.ipa = call::or_empty(self.get_ipa(word))?;
+    }
+    if opts.definitions {
+      report.definitions = call::or_empty(self.get_definitions(word))?;
+    }
+    if opts.examples != 0 {
+      let query = ExamplesQuery::new().limit(opts.examples);
+      report.examples = call::or_empty(self.get_examples_with(word, &query))?;
+    }
+    Ok(pretty::format_report(&report, opts))
   }
 }
@@ -0,0 +1,109 @@
+//! Paging through list endpoints, shared by the blocking iterator and the
+//! async stream.
+
+#[cfg(feature = "blocking")]
+use crate::{Example, Wordnik};
+use crate::{ExamplesQuery, Paged, WordnikError};
+
+/// The number of examples fetched per request unless the query says
+/// otherwise.
+const PAGE_SIZE: usize = 50;
+
+/// Tracks where the next page starts and whether there is one. The API
+/// doesn't always report a total, so paging stops at the first short page,
+/// or once the query's limit is reached.
+pub(crate) struct Pager {
+  query: ExamplesQuery,
+  next: usize,
+  page_size: usize,
+  /// How many more examples may be fetched, if the query is limited.
+  remaining: Option<usize>,
+  done: bool,
+}
+
+impl Pager {
+  pub(crate) fn new(query: &ExamplesQuery) -> Pager {
+    Pager {
+      query: query.clone(),
+      next: query.skip.unwrap_or(0),
+      page_size: query.page_size.unwrap_or(PAGE_SIZE).max(1),
+      remaining: query.limit,
+      done: query.limit == Some(0),
+    }
+  }
+
+  /// How many examples to ask for in the next page.
+  fn limit(&self) -> usize {
+    self
+      .remaining
+      .map_or(self.page_size, |n| n.min(self.page_size))
+  }
+
+  /// The query for the next page, or `None` once the results are
+  /// exhausted.
+  pub(crate) fn next_query(&self) -> Option<ExamplesQuery> {
+    if self.done {
+      return None;
+    }
+    Some(self.query.clone().skip(self.next).limit(self.limit()))
+  }
+
+  /// Record the outcome of fetching the page from
+  /// [next_query](Self::next_query). Paging stops after an error.
+  pub(crate) fn advance<T>(&mut self, res: &Result<Paged<T>, WordnikError>) {
+    match res {
+      Ok(page) => {
+        let limit = self.limit();
+        self.next += page.items.len();
+        self.remaining =
+          self.remaining.map(|n| n.saturating_sub(page.items.len()));
+        self.done = page.items.len() < limit || self.remaining == Some(0);
+      }
+      Err(_) => self.done = true,
+    }
+  }
+}
+
+#[cfg(feature = "blocking")]
+pub(crate) struct ExamplesIter<'a> {
+  api: &'a Wordnik,
+  word: String,
+  pager: Pager,
+  page: std::vec::IntoIter<Example>,
+}
+
+#[cfg(feature = "blocking")]
+impl<'a> ExamplesIter<'a> {
+  pub(crate) fn new(
+    api: &'a Wordnik,
+    word: &str,
+    query: &ExamplesQuery,
+  ) -> ExamplesIter<'a> {
+    ExamplesIter {
+      api,
+      word: word.to_string(),
+      pager: Pager::new(query),
+      page: Vec::new().into_iter(),
+    }
+  }
+}
+
+#[cfg(feature = "blocking")]
+impl Iterator for ExamplesIter<'_> {
+  type Item = Result<Example, WordnikError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      if let Some(example) = self.page.next() {
+        return Some(Ok(example));
+      }
+      let query = self.pager.next_query()?;
+      let res = self.api.get_examples_paged(&self.word, &query);
+      self.pager.advance(&res);
+      match res {
+        Ok(page) => self.page = page.items.into_iter(),
+        Err(e) => return Some(Err(e)),
+      }
+    }
+  }
+}
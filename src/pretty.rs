@@ -0,0 +1,925 @@
+//! Formatting of API responses as human-readable text.
+
+use crate::{
+  dedup_definitions, group_by_source, Definition, Example, Syllable, WordReport,
+};
+
+/// Options for [get_full_report_pretty_with](crate::Wordnik::get_full_report_pretty_with)
+/// and [get_definitions_pretty_with](crate::Wordnik::get_definitions_pretty_with).
+///
+/// By default every section is shown, with two examples, and the text is
+/// not wrapped. All definitions are shown, in bulleted lists under their
+/// attributions.
+///
+/// # Example
+///
+/// ```
+/// use wordnik::PrettyOptions;
+///
+/// let opts = PrettyOptions::new().hyphenation(false).examples(3).textwidth(80);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrettyOptions {
+  pub(crate) hyphenation: bool,
+  pub(crate) pronunciation: bool,
+  pub(crate) definitions: bool,
+  pub(crate) examples: usize,
+  pub(crate) textwidth: usize,
+  pub(crate) dedup: bool,
+  pub(crate) color: bool,
+  pub(crate) group_by: GroupBy,
+  pub(crate) sources: Vec<String>,
+  pub(crate) excluded_sources: Vec<String>,
+  pub(crate) max_per_source: Option<usize>,
+  pub(crate) max_definitions: Option<usize>,
+  pub(crate) numbered: bool,
+  pub(crate) attributions: bool,
+  pub(crate) definition_examples: bool,
+}
+
+/// How [PrettyOptions::group_by] groups the definitions.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GroupBy {
+  /// Under the attribution of their dictionary, in the order the
+  /// dictionaries first appear.
+  #[default]
+  Source,
+  /// Under their part of speech: nouns, verbs, adjectives and adverbs,
+  /// then the other parts of speech in alphabetical order, then the
+  /// definitions without one under "other". Each definition is followed
+  /// by its dictionary in parentheses.
+  PartOfSpeech,
+}
+
+impl Default for PrettyOptions {
+  fn default() -> PrettyOptions {
+    PrettyOptions {
+      hyphenation: true,
+      pronunciation: true,
+      definitions: true,
+      examples: 2,
+      textwidth: 0,
+      dedup: false,
+      color: false,
+      group_by: GroupBy::Source,
+      sources: Vec::new(),
+      excluded_sources: Vec::new(),
+      max_per_source: None,
+      max_definitions: None,
+      numbered: false,
+      attributions: true,
+      definition_examples: false,
+    }
+  }
+}
+
+impl PrettyOptions {
+  pub fn new() -> PrettyOptions {
+    PrettyOptions::default()
+  }
+
+  /// Show the word split into syllables.
+  pub fn hyphenation(mut self, show: bool) -> PrettyOptions {
+    self.hyphenation = show;
+    self
+  }
+
+  /// Show the IPA pronunciations.
+  pub fn pronunciation(mut self, show: bool) -> PrettyOptions {
+    self.pronunciation = show;
+    self
+  }
+
+  /// Show the definitions, grouped by source.
+  pub fn definitions(mut self, show: bool) -> PrettyOptions {
+    self.definitions = show;
+    self
+  }
+
+  /// Show at most this many examples. Pass 0 to hide them.
+  pub fn examples(mut self, count: usize) -> PrettyOptions {
+    self.examples = count;
+    self
+  }
+
+  /// The column at which to wrap the text. Pass 0 to not wrap at all.
+  pub fn textwidth(mut self, textwidth: usize) -> PrettyOptions {
+    self.textwidth = textwidth;
+    self
+  }
+
+  /// Hide definitions repeating an earlier one, see [dedup_definitions].
+  pub fn dedup(mut self, dedup: bool) -> PrettyOptions {
+    self.dedup = dedup;
+    self
+  }
+
+  /// Style the text with ANSI escape codes for terminals: the word bold,
+  /// the parts of speech colored and the attributions dimmed. Off by
+  /// default. The library doesn't check whether the output is a
+  /// terminal; that is up to the caller.
+  ///
+  /// Apart from the escape codes, the text is the same as without color.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use std::io::IsTerminal;
+  /// use wordnik::{PrettyOptions, Wordnik};
+  ///
+  /// # let api = Wordnik::with_requester(
+  /// #   "YOUR_API_KEY".to_string(),
+  /// #   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// #   |url: &str, _: &HeaderMap| {
+  /// #     Ok(match url.contains("/definitions") {
+  /// #       true => json!([{
+  /// #         "word": "run",
+  /// #         "text": "To move swiftly.",
+  /// #         "partOfSpeech": "verb",
+  /// #         "attributionText": "from Wordnet",
+  /// #         "sourceDictionary": "wordnet",
+  /// #         "attributionUrl": "",
+  /// #         "wordnikUrl": ""
+  /// #       }]),
+  /// #       false => json!([]),
+  /// #     })
+  /// #   },
+  /// # );
+  /// let opts = PrettyOptions::new().color(std::io::stdout().is_terminal());
+  /// println!("{}", api.get_full_report_pretty_with("run", &opts).unwrap());
+  ///
+  /// let colored = api
+  ///   .get_full_report_pretty_with("run", &PrettyOptions::new().color(true))
+  ///   .unwrap();
+  /// assert!(colored.starts_with("\x1b[1mrun\x1b[0m\n"));
+  /// let plain = api
+  ///   .get_full_report_pretty_with("run", &PrettyOptions::new())
+  ///   .unwrap();
+  /// let mut stripped = String::new();
+  /// for (i, part) in colored.split('\x1b').enumerate() {
+  ///   stripped += if i == 0 { part } else { &part[part.find('m').unwrap() + 1..] };
+  /// }
+  /// assert_eq!(stripped, plain);
+  /// ```
+  pub fn color(mut self, color: bool) -> PrettyOptions {
+    self.color = color;
+    self
+  }
+
+  /// Group the definitions by source, the default, or by part of speech.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{GroupBy, PrettyOptions, Wordnik};
+  ///
+  /// # let def = |text: &str, part_of_speech: Option<&str>, source: &str| {
+  /// #   json!({
+  /// #     "word": "run",
+  /// #     "text": text,
+  /// #     "partOfSpeech": part_of_speech,
+  /// #     "attributionText": "",
+  /// #     "sourceDictionary": source,
+  /// #     "attributionUrl": "",
+  /// #     "wordnikUrl": ""
+  /// #   })
+  /// # };
+  /// # let definitions = json!([
+  /// #   def("Flowing.", Some("adjective"), "century"),
+  /// #   def("To move swiftly.", Some("verb"), "wordnet"),
+  /// #   def("A score in baseball.", Some("noun"), "ahd-5"),
+  /// #   def("To flee.", Some("verb"), "gcide"),
+  /// #   def("Run!", Some("interjection"), "wiktionary"),
+  /// #   def("A series of stitches.", None, "century"),
+  /// # ]);
+  /// # let api = Wordnik::with_requester(
+  /// #   "YOUR_API_KEY".to_string(),
+  /// #   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// #   move |url: &str, _: &HeaderMap| {
+  /// #     Ok(match url.contains("/definitions") {
+  /// #       true => definitions.clone(),
+  /// #       false => json!([]),
+  /// #     })
+  /// #   },
+  /// # );
+  /// let opts = PrettyOptions::new().group_by(GroupBy::PartOfSpeech);
+  /// let report = api.get_full_report_pretty_with("run", &opts).unwrap();
+  /// let expected = [
+  ///   "run",
+  ///   "",
+  ///   "noun",
+  ///   "  * A score in baseball. (ahd-5)",
+  ///   "",
+  ///   "verb",
+  ///   "  * To move swiftly. (wordnet)",
+  ///   "  * To flee. (gcide)",
+  ///   "",
+  ///   "adjective",
+  ///   "  * Flowing. (century)",
+  ///   "",
+  ///   "interjection",
+  ///   "  * Run! (wiktionary)",
+  ///   "",
+  ///   "other",
+  ///   "  * A series of stitches. (century)",
+  ///   "",
+  ///   "",
+  /// ];
+  /// assert_eq!(report, expected.join("\n"));
+  /// ```
+  pub fn group_by(mut self, group_by: GroupBy) -> PrettyOptions {
+    self.group_by = group_by;
+    self
+  }
+
+  /// Only show definitions from these
+  /// [source dictionaries](Definition::source_dictionary), e.g. "ahd-5".
+  /// Pass an empty slice to show all.
+  pub fn sources(mut self, sources: &[&str]) -> PrettyOptions {
+    self.sources = sources.iter().map(|s| s.to_string()).collect();
+    self
+  }
+
+  /// Hide definitions from these source dictionaries.
+  pub fn exclude_sources(mut self, sources: &[&str]) -> PrettyOptions {
+    self.excluded_sources = sources.iter().map(|s| s.to_string()).collect();
+    self
+  }
+
+  /// Show at most this many definitions of each source dictionary, the
+  /// first ones the API returned.
+  pub fn max_per_source(mut self, max: usize) -> PrettyOptions {
+    self.max_per_source = Some(max);
+    self
+  }
+
+  /// Show at most this many definitions in all, the first ones the API
+  /// returned.
+  pub fn max_definitions(mut self, max: usize) -> PrettyOptions {
+    self.max_definitions = Some(max);
+    self
+  }
+
+  /// Number the definitions of each group from 1 instead of bulleting
+  /// them.
+  pub fn numbered(mut self, numbered: bool) -> PrettyOptions {
+    self.numbered = numbered;
+    self
+  }
+
+  /// Show the attributions. On by default.
+  pub fn attributions(mut self, show: bool) -> PrettyOptions {
+    self.attributions = show;
+    self
+  }
+
+  /// Show the first of the [example_uses](Definition::example_uses) of
+  /// each definition in quotes, on the lines under it, aligned with its
+  /// text. Definitions without one are shown as usual. Off by default.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use reqwest::header::HeaderMap;
+  /// # use serde_json::json;
+  /// use wordnik::{PrettyOptions, Wordnik};
+  ///
+  /// # let definitions = json!([
+  /// #   {
+  /// #     "word": "run",
+  /// #     "text": "To move swiftly on foot.",
+  /// #     "partOfSpeech": "verb",
+  /// #     "attributionText": "from Wordnet",
+  /// #     "sourceDictionary": "wordnet",
+  /// #     "attributionUrl": "",
+  /// #     "wordnikUrl": "",
+  /// #     "exampleUses": [{ "text": "She <em>ran</em> all the way to the station." }]
+  /// #   },
+  /// #   {
+  /// #     "word": "run",
+  /// #     "text": "To flee.",
+  /// #     "partOfSpeech": "verb",
+  /// #     "attributionText": "from Wordnet",
+  /// #     "sourceDictionary": "wordnet",
+  /// #     "attributionUrl": "",
+  /// #     "wordnikUrl": ""
+  /// #   },
+  /// # ]);
+  /// # let api = Wordnik::with_requester(
+  /// #   "YOUR_API_KEY".to_string(),
+  /// #   "https://api.wordnik.com/v4/word.json/".to_string(),
+  /// #   move |_: &str, _: &HeaderMap| Ok(definitions.clone()),
+  /// # );
+  /// let opts = PrettyOptions::new().definition_examples(true).textwidth(30);
+  /// let pretty = api.get_definitions_pretty_with("run", &opts).unwrap();
+  /// let expected = [
+  ///   "from Wordnet",
+  ///   "  * verb To move swiftly on",
+  ///   "         foot.",
+  ///   "         \"She ran all the way",
+  ///   "         to the station.\"",
+  ///   "  * verb To flee.",
+  ///   "",
+  ///   "",
+  /// ];
+  /// assert_eq!(pretty, expected.join("\n"));
+  /// ```
+  pub fn definition_examples(mut self, show: bool) -> PrettyOptions {
+    self.definition_examples = show;
+    self
+  }
+}
+
+/// Options for [Definition::pretty].
+///
+/// By default the part of speech is shown and the attribution is not.
+///
+/// # Example
+///
+/// ```
+/// use wordnik::DefinitionOptions;
+///
+/// let opts = DefinitionOptions::new().part_of_speech(false).attribution(true);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefinitionOptions {
+  pub(crate) part_of_speech: bool,
+  pub(crate) attribution: bool,
+  pub(crate) color: bool,
+}
+
+impl Default for DefinitionOptions {
+  fn default() -> DefinitionOptions {
+    DefinitionOptions {
+      part_of_speech: true,
+      attribution: false,
+      color: false,
+    }
+  }
+}
+
+impl DefinitionOptions {
+  pub fn new() -> DefinitionOptions {
+    DefinitionOptions::default()
+  }
+
+  /// Start with the part of speech, if there is one.
+  pub fn part_of_speech(mut self, show: bool) -> DefinitionOptions {
+    self.part_of_speech = show;
+    self
+  }
+
+  /// Add the attribution on a line of its own, if there is one.
+  pub fn attribution(mut self, show: bool) -> DefinitionOptions {
+    self.attribution = show;
+    self
+  }
+
+  /// Color the part of speech and dim the attribution with ANSI escape
+  /// codes, see [PrettyOptions::color].
+  pub fn color(mut self, color: bool) -> DefinitionOptions {
+    self.color = color;
+    self
+  }
+}
+
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// `text` in `style`, if `color` is set and there is any text.
+fn paint(text: &str, style: &str, color: bool) -> String {
+  if color && !text.is_empty() {
+    [style, text, RESET].concat()
+  } else {
+    text.to_string()
+  }
+}
+
+/// Format one definition, without a trailing newline.
+pub(crate) fn format_definition(
+  definition: &Definition,
+  opts: &DefinitionOptions,
+) -> String {
+  let mut s = String::new();
+  match definition.part_of_speech.as_deref() {
+    Some(part_of_speech)
+      if opts.part_of_speech && !part_of_speech.is_empty() =>
+    {
+      s = s + &paint(part_of_speech, CYAN, opts.color) + " ";
+    }
+    _ => {}
+  }
+  s += &definition.plain_text();
+  if opts.attribution && !definition.attribution_text.is_empty() {
+    s = s + "\n" + &paint(&definition.attribution_text, DIM, opts.color);
+  }
+  s
+}
+
+/// The sections of a full report; the ones turned off in the options are
+/// left empty.
+pub(crate) struct Report<'a> {
+  pub(crate) word: &'a str,
+  pub(crate) hyphenation: Vec<Syllable>,
+  pub(crate) ipa: Vec<String>,
+  pub(crate) definitions: Vec<Definition>,
+  pub(crate) examples: Vec<Example>,
+}
+
+pub(crate) fn format_report(report: &Report, opts: &PrettyOptions) -> String {
+  let mut s = paint(report.word, BOLD, opts.color) + "\n";
+  if !report.hyphenation.is_empty() {
+    let syllables: Vec<&str> =
+      report.hyphenation.iter().map(|s| s.text.as_str()).collect();
+    s = s + &wrap(&syllables.join("·"), opts.textwidth) + "\n";
+  }
+  if !report.ipa.is_empty() {
+    s = s + &wrap(&report.ipa.join(", "), opts.textwidth) + "\n";
+  }
+  if !report.definitions.is_empty() {
+    s = s + "\n" + &format_definitions(&report.definitions, opts);
+  } else {
+    s += "\n";
+  }
+  if !report.examples.is_empty() {
+    s += "Examples\n";
+    for example in report.examples.iter().take(opts.examples) {
+      s = s + &wrap_item("  * ", &example.plain_text(), opts.textwidth) + "\n";
+    }
+  }
+
+  s
+}
+
+pub(crate) fn format_word_report(
+  report: &WordReport,
+  opts: &PrettyOptions,
+) -> String {
+  let mut s = paint(&report.word, BOLD, opts.color) + "\n";
+  if let Some(hyphenation) = report.hyphenation.as_ref() {
+    let syllables: Vec<&str> =
+      hyphenation.iter().map(|s| s.text.as_str()).collect();
+    if !syllables.is_empty() {
+      s = s + &wrap(&syllables.join("·"), opts.textwidth) + "\n";
+    }
+  }
+  if let Some(pronunciations) = report.pronunciations.as_ref() {
+    let raw: Vec<&str> =
+      pronunciations.iter().map(|p| p.raw.as_str()).collect();
+    if !raw.is_empty() {
+      s = s + &wrap(&raw.join(", "), opts.textwidth) + "\n";
+    }
+  }
+  s += "\n";
+  if let Some(definitions) = report.definitions.as_ref() {
+    s += &format_definitions(definitions, opts);
+  }
+  let width = opts.textwidth;
+  if let Some(etymologies) = report.etymologies.as_ref() {
+    let etymologies = etymologies.iter().map(|e| e.to_plain_text()).collect();
+    s += &format_section("Etymology", etymologies, width);
+  }
+  if let Some(example) = report.top_example.as_ref() {
+    s += &format_section("Example", vec![example.plain_text()], width);
+  }
+  if let Some(related_words) = report.related_words.as_ref() {
+    let related = related_words
+      .iter()
+      .filter(|r| !r.words.is_empty())
+      .map(|r| format!("{}: {}", r.relationship_type, r.words.join(", ")))
+      .collect();
+    s += &format_section("Related words", related, width);
+  }
+  if let Some(audio) = report.audio.as_ref() {
+    let urls = audio.iter().map(|a| a.file_url.clone()).collect();
+    s += &format_section("Audio", urls, width);
+  }
+  let mut counts = Vec::new();
+  if let Some(score) = report.scrabble_score {
+    counts.push(format!("Scrabble score: {}", score.value));
+  }
+  if let Some(frequency) = report.frequency.as_ref() {
+    counts.push(format!("Frequency: {}", frequency.total_count));
+  }
+  if !counts.is_empty() {
+    s = s + &counts.join("\n") + "\n\n";
+  }
+  let errors = report
+    .errors
+    .iter()
+    .map(|(operation, e)| format!("{}: {}", operation, e))
+    .collect();
+  s + &format_section("Errors", errors, width)
+}
+
+/// A heading and a bulleted list of `items`, or nothing if there are none.
+fn format_section(
+  heading: &str,
+  items: Vec<String>,
+  textwidth: usize,
+) -> String {
+  if items.is_empty() {
+    return String::new();
+  }
+  let mut s = heading.to_string() + "\n";
+  for item in items {
+    s = s + &wrap_item("  * ", &item, textwidth) + "\n";
+  }
+  s + "\n"
+}
+
+/// Format the definitions chosen by `opts`, grouped as it says.
+pub(crate) fn format_definitions(
+  definitions: &[Definition],
+  opts: &PrettyOptions,
+) -> String {
+  let definitions = select_definitions(definitions, opts);
+  match opts.group_by {
+    GroupBy::Source => format_definitions_by_source(definitions, opts),
+    GroupBy::PartOfSpeech => {
+      format_definitions_by_part_of_speech(&definitions, opts)
+    }
+  }
+}
+
+/// The definitions to show: those of the chosen dictionaries, without
+/// duplicates if asked, and up to the maximums, in the API's order.
+fn select_definitions(
+  definitions: &[Definition],
+  opts: &PrettyOptions,
+) -> Vec<Definition> {
+  let mut definitions: Vec<Definition> = definitions
+    .iter()
+    .filter(|d| {
+      let source = &d.source_dictionary;
+      (opts.sources.is_empty() || opts.sources.contains(source))
+        && !opts.excluded_sources.contains(source)
+    })
+    .cloned()
+    .collect();
+  if opts.dedup {
+    dedup_definitions(&mut definitions);
+  }
+  if let Some(max) = opts.max_per_source {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    definitions.retain(|d| {
+      let source = &d.source_dictionary;
+      let count = match counts.iter_mut().find(|(s, _)| s == source) {
+        Some((_, count)) => {
+          *count += 1;
+          *count
+        }
+        None => {
+          counts.push((source.clone(), 1));
+          1
+        }
+      };
+      count <= max
+    });
+  }
+  if let Some(max) = opts.max_definitions {
+    definitions.truncate(max);
+  }
+  definitions
+}
+
+/// The bullet of the `i`th item of a group.
+fn bullet(opts: &PrettyOptions, i: usize) -> String {
+  match opts.numbered {
+    true => format!("  {}. ", i + 1),
+    false => "  * ".to_string(),
+  }
+}
+
+fn format_definitions_by_source(
+  definitions: Vec<Definition>,
+  opts: &PrettyOptions,
+) -> String {
+  let mut s = "".to_string();
+  for (_, definitions) in group_by_source(definitions) {
+    if opts.attributions {
+      let heading = wrap(&definitions[0].attribution_text, opts.textwidth);
+      s = s + &paint(&heading, DIM, opts.color) + "\n";
+    }
+    for (i, d) in definitions.iter().enumerate() {
+      let mut lead = bullet(opts, i);
+      match d.part_of_speech.as_deref() {
+        Some(part_of_speech) if !part_of_speech.is_empty() => {
+          lead = lead + &paint(part_of_speech, CYAN, opts.color) + " ";
+        }
+        _ => {}
+      }
+      s = s + &wrap_item(&lead, &d.plain_text(), opts.textwidth) + "\n";
+      s += &format_example_use(d, &lead, opts);
+    }
+    s += "\n";
+  }
+
+  s
+}
+
+/// The first example use of `definition` in quotes, indented by the
+/// width of `lead`, if [PrettyOptions::definition_examples] is on and it
+/// has one.
+fn format_example_use(
+  definition: &Definition,
+  lead: &str,
+  opts: &PrettyOptions,
+) -> String {
+  if !opts.definition_examples {
+    return String::new();
+  }
+  let example = definition
+    .example_uses
+    .iter()
+    .map(|e| decode_entities(&strip_tags(&e.text)))
+    .find(|text| !text.is_empty());
+  match example {
+    Some(text) => {
+      let indent = " ".repeat(textwrap::core::display_width(lead));
+      wrap_item(&indent, &format!("\"{}\"", text), opts.textwidth) + "\n"
+    }
+    None => String::new(),
+  }
+}
+
+/// Format `definitions` grouped by part of speech, see
+/// [GroupBy::PartOfSpeech].
+fn format_definitions_by_part_of_speech(
+  definitions: &[Definition],
+  opts: &PrettyOptions,
+) -> String {
+  let mut groups: Vec<(&str, Vec<&Definition>)> = Vec::new();
+  for d in definitions {
+    let part_of_speech = match d.part_of_speech.as_deref() {
+      Some(part_of_speech) if !part_of_speech.is_empty() => part_of_speech,
+      _ => "other",
+    };
+    match groups.iter_mut().find(|(p, _)| *p == part_of_speech) {
+      Some((_, v)) => v.push(d),
+      None => groups.push((part_of_speech, vec![d])),
+    }
+  }
+  groups.sort_by_key(|(part_of_speech, _)| {
+    let rank = ["noun", "verb", "adjective", "adverb"]
+      .iter()
+      .position(|p| p == part_of_speech)
+      .unwrap_or(match *part_of_speech {
+        "other" => 5,
+        _ => 4,
+      });
+    (rank, *part_of_speech)
+  });
+
+  let mut s = "".to_string();
+  for (part_of_speech, definitions) in groups {
+    s = s + &paint(part_of_speech, CYAN, opts.color) + "\n";
+    for (i, d) in definitions.iter().enumerate() {
+      let mut text = d.plain_text();
+      if opts.attributions && !d.source_dictionary.is_empty() {
+        let source = format!("({})", d.source_dictionary);
+        text = text + " " + &paint(&source, DIM, opts.color);
+      }
+      let lead = bullet(opts, i);
+      s = s + &wrap_item(&lead, &text, opts.textwidth) + "\n";
+      s += &format_example_use(d, &lead, opts);
+    }
+    s += "\n";
+  }
+  s
+}
+
+/// Wrap `text` at `textwidth` columns, or not at all if it is 0.
+fn wrap(text: &str, textwidth: usize) -> String {
+  match textwidth {
+    0 => text.to_string(),
+    _ => textwrap::fill(text, textwidth),
+  }
+}
+
+/// `lead`, e.g. a bullet, followed by `text` wrapped at `textwidth`
+/// columns, indenting the lines after the first to align with the start
+/// of the text. Widths are counted in columns, so wide and combining
+/// characters and escape codes in `lead` are handled.
+fn wrap_item(lead: &str, text: &str, textwidth: usize) -> String {
+  if textwidth == 0 || text.is_empty() {
+    return lead.to_string() + text;
+  }
+  let indent = " ".repeat(textwrap::core::display_width(lead));
+  let opts = textwrap::Options::new(textwidth)
+    .initial_indent(lead)
+    .subsequent_indent(&indent);
+  textwrap::fill(text, opts)
+}
+
+/// Format the definitions of `word` as Markdown: a heading for the word,
+/// a subheading per attribution and a numbered list of its definitions.
+pub(crate) fn format_definitions_markdown(
+  word: &str,
+  definitions: &[Definition],
+) -> String {
+  let mut s = format!("# {}\n", escape_markdown(word));
+  for (source, definitions) in group_by_source(definitions.to_vec()) {
+    s += "\n";
+    let heading = match definitions[0].attribution_text.as_str() {
+      "" => source.as_str(),
+      attribution_text => attribution_text,
+    };
+    if !heading.is_empty() {
+      s = s + "### " + &escape_markdown(heading) + "\n\n";
+    }
+    for (i, def) in definitions.iter().enumerate() {
+      s += &format!("{}. ", i + 1);
+      match def.part_of_speech.as_deref() {
+        Some(part_of_speech) if !part_of_speech.is_empty() => {
+          s = s + "**" + &escape_markdown(part_of_speech) + "** ";
+        }
+        _ => {}
+      }
+      s = s + &markdown_text(def.definition.as_deref().unwrap_or_default());
+      s += "\n";
+    }
+  }
+  s
+}
+
+/// Convert the markup of a definition to Markdown: `<em>` and `<i>`
+/// become `*italics*`, `<strong>` and `<b>` become `**bold**`, and other
+/// tags are dropped like in [strip_tags]. Entities are decoded, and the
+/// text is escaped so that it renders as it reads.
+fn markdown_text(text: &str) -> String {
+  let mut markdown = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some(start) = rest.find('<') {
+    markdown += &escape_markdown(&decode_entities(&rest[..start]));
+    let tag = &rest[start + 1..];
+    let name = tag
+      .trim_start_matches('/')
+      .split(|c: char| !c.is_ascii_alphanumeric())
+      .next()
+      .unwrap_or_default();
+    match tag.find(['<', '>']) {
+      Some(end) if !name.is_empty() && tag[end..].starts_with('>') => {
+        let self_closing = tag[..end].ends_with('/');
+        match name.to_ascii_lowercase().as_str() {
+          _ if self_closing => {}
+          "em" | "i" => markdown.push('*'),
+          "strong" | "b" => markdown.push_str("**"),
+          _ => {}
+        }
+        rest = &tag[end + 1..];
+      }
+      _ => {
+        markdown.push_str("\\<");
+        rest = tag;
+      }
+    }
+  }
+  markdown += &escape_markdown(&decode_entities(rest));
+  markdown.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Backslash-escape the characters Markdown gives a meaning to inline.
+fn escape_markdown(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    if matches!(
+      c,
+      '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~'
+    ) {
+      escaped.push('\\');
+    }
+    escaped.push(c);
+  }
+  escaped
+}
+
+/// Remove markup tags such as `<xref>` and `<em/>` from `text`, keeping
+/// the text inside them, and collapse runs of whitespace. A `<`
+/// that doesn't start a tag, e.g. in "a < b", is kept.
+pub(crate) fn strip_tags(text: &str) -> String {
+  let mut plain = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some(start) = rest.find('<') {
+    plain.push_str(&rest[..start]);
+    let tag = &rest[start + 1..];
+    let is_tag = tag
+      .trim_start_matches('/')
+      .starts_with(|c: char| c.is_ascii_alphabetic());
+    match tag.find(['<', '>']) {
+      Some(end) if is_tag && tag[end..].starts_with('>') => {
+        rest = &tag[end + 1..];
+      }
+      _ => {
+        plain.push('<');
+        rest = tag;
+      }
+    }
+  }
+  plain.push_str(rest);
+  plain.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Decode HTML entities such as `&amp;`, `&eacute;` and `&#233;` in
+/// `text`, in a single pass so that `&amp;amp;` becomes `&amp;`. Unknown
+/// entities are kept as they are.
+///
+/// The `plain_text` methods, e.g. [Definition::plain_text], already
+/// decode the text they return; this is for the other fields.
+///
+/// # Example
+///
+/// ```
+/// use wordnik::decode_entities;
+///
+/// assert_eq!(decode_entities("&quot;Fish &amp; chips&quot;"), "\"Fish & chips\"");
+/// assert_eq!(decode_entities("caf&#233; &bogus;"), "café &bogus;");
+/// ```
+pub fn decode_entities(text: &str) -> String {
+  let mut decoded = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some(start) = rest.find('&') {
+    decoded.push_str(&rest[..start]);
+    rest = &rest[start..];
+    let entity = rest[1..]
+      .find(';')
+      .map(|end| &rest[1..end + 1])
+      .filter(|name| name.len() <= 10);
+    match entity.and_then(entity_char) {
+      Some(c) => {
+        decoded.push(c);
+        rest = &rest[entity.map_or(0, str::len) + 2..];
+      }
+      None => {
+        decoded.push('&');
+        rest = &rest[1..];
+      }
+    }
+  }
+  decoded.push_str(rest);
+  decoded
+}
+
+/// The character an entity, without its `&` and `;`, stands for.
+fn entity_char(name: &str) -> Option<char> {
+  if let Some(number) = name.strip_prefix('#') {
+    let code = match number.strip_prefix(['x', 'X']) {
+      Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+      None => number.parse().ok()?,
+    };
+    return char::from_u32(code);
+  }
+  let c = match name {
+    "amp" => '&',
+    "lt" => '<',
+    "gt" => '>',
+    "quot" => '"',
+    "apos" => '\'',
+    "nbsp" => '\u{a0}',
+    "ndash" => '–',
+    "mdash" => '—',
+    "hellip" => '…',
+    "lsquo" => '‘',
+    "rsquo" => '’',
+    "ldquo" => '“',
+    "rdquo" => '”',
+    "laquo" => '«',
+    "raquo" => '»',
+    "middot" => '·',
+    "deg" => '°',
+    "copy" => '©',
+    "reg" => '®',
+    "times" => '×',
+    "aacute" => 'á',
+    "agrave" => 'à',
+    "acirc" => 'â',
+    "auml" => 'ä',
+    "aelig" => 'æ',
+    "ccedil" => 'ç',
+    "eacute" => 'é',
+    "egrave" => 'è',
+    "ecirc" => 'ê',
+    "euml" => 'ë',
+    "iacute" => 'í',
+    "icirc" => 'î',
+    "iuml" => 'ï',
+    "ntilde" => 'ñ',
+    "oacute" => 'ó',
+    "ocirc" => 'ô',
+    "ouml" => 'ö',
+    "oelig" => 'œ',
+    "uacute" => 'ú',
+    "ugrave" => 'ù',
+    "ucirc" => 'û',
+    "uuml" => 'ü',
+    _ => return None,
+  };
+  Some(c)
+}
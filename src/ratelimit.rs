@@ -0,0 +1,169 @@
+//! Staying under the API's rate limits: client-side throttles, turned on
+//! with [WordnikBuilder::rate_limit](crate::WordnikBuilder::rate_limit)
+//! and
+//! [WordnikBuilder::min_request_interval](crate::WordnikBuilder::min_request_interval),
+//! and the limits the API last reported.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use reqwest::header::HeaderMap;
+
+use crate::{Fetched, WordnikError};
+
+/// The rate limits reported by the API in the headers of a response.
+/// Limits it didn't report are `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RateLimitStatus {
+  /// The requests allowed per minute.
+  pub limit_minute: Option<u32>,
+  /// The requests left in the current minute.
+  pub remaining_minute: Option<u32>,
+  /// The requests allowed per hour.
+  pub limit_hour: Option<u32>,
+  /// The requests left in the current hour.
+  pub remaining_hour: Option<u32>,
+  /// The time until the limits reset, as of when the response arrived.
+  pub reset: Option<Duration>,
+}
+
+impl RateLimitStatus {
+  /// Read the `X-RateLimit-*` and `RateLimit-Reset` headers, or return
+  /// `None` if there are none, e.g. for a custom
+  /// [Requester](crate::Requester) filling in [Fetched].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use reqwest::header::{HeaderMap, HeaderValue};
+  /// use wordnik::RateLimitStatus;
+  ///
+  /// let mut headers = HeaderMap::new();
+  /// headers.insert("x-ratelimit-remaining-minute", HeaderValue::from_static("14"));
+  /// let status = RateLimitStatus::from_headers(&headers).unwrap();
+  /// assert_eq!(status.remaining_minute, Some(14));
+  /// assert_eq!(status.limit_hour, None);
+  /// assert_eq!(RateLimitStatus::from_headers(&HeaderMap::new()), None);
+  /// ```
+  pub fn from_headers(headers: &HeaderMap) -> Option<RateLimitStatus> {
+    let number = |name: &str| -> Option<u32> {
+      headers.get(name)?.to_str().ok()?.trim().parse().ok()
+    };
+    let status = RateLimitStatus {
+      limit_minute: number("x-ratelimit-limit-minute"),
+      remaining_minute: number("x-ratelimit-remaining-minute"),
+      limit_hour: number("x-ratelimit-limit-hour"),
+      remaining_hour: number("x-ratelimit-remaining-hour"),
+      reset: number("ratelimit-reset").map(|s| Duration::from_secs(s.into())),
+    };
+    (status != RateLimitStatus::default()).then_some(status)
+  }
+}
+
+/// Read the wait asked for by a `Retry-After` header, given in seconds or
+/// as an HTTP date. A date in the past means no wait.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+  let value = headers.get("retry-after")?.to_str().ok()?.trim();
+  if let Ok(seconds) = value.parse() {
+    return Some(Duration::from_secs(seconds));
+  }
+  let date = httpdate::parse_http_date(value).ok()?;
+  Some(date.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+/// The rate limit state of a client, shared with its clones.
+#[derive(Clone, Default)]
+pub(crate) struct RateLimits {
+  limiters: Arc<Vec<RateLimiter>>,
+  last: Arc<Mutex<Option<RateLimitStatus>>>,
+}
+
+impl RateLimits {
+  pub(crate) fn new(limiters: Vec<RateLimiter>) -> RateLimits {
+    RateLimits {
+      limiters: Arc::new(limiters),
+      last: Arc::default(),
+    }
+  }
+
+  /// Reserve a slot with every throttle, returning how long to wait for
+  /// the latest of them.
+  fn reserve(&self) -> Duration {
+    self
+      .limiters
+      .iter()
+      .map(RateLimiter::reserve)
+      .max()
+      .unwrap_or_default()
+  }
+
+  /// Wait for the throttles, if any, sleeping the thread.
+  #[cfg(feature = "blocking")]
+  pub(crate) fn wait(&self) {
+    let wait = self.reserve();
+    if !wait.is_zero() {
+      std::thread::sleep(wait);
+    }
+  }
+
+  /// Wait for the throttles, if any, without blocking the thread.
+  #[cfg(feature = "async")]
+  pub(crate) async fn wait_async(&self) {
+    let wait = self.reserve();
+    if !wait.is_zero() {
+      futures_timer::Delay::new(wait).await;
+    }
+  }
+
+  /// Remember the limits reported with a response.
+  pub(crate) fn record(&self, res: &Result<Fetched, WordnikError>) {
+    let status = match res {
+      Ok(Fetched::Body { rate_limit, .. }) => rate_limit,
+      Ok(Fetched::NotModified { rate_limit }) => rate_limit,
+      Err(_) => &None,
+    };
+    if let Some(status) = status {
+      *self.last.lock().unwrap() = Some(*status);
+    }
+  }
+
+  pub(crate) fn last(&self) -> Option<RateLimitStatus> {
+    *self.last.lock().unwrap()
+  }
+}
+
+/// Allows at most `max_requests` requests in any `window`. Requests over
+/// the budget are given a slot in the future, and the caller waits for it.
+pub(crate) struct RateLimiter {
+  max_requests: usize,
+  window: Duration,
+  /// When the requests in the current window were, or will be, sent.
+  slots: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+  pub(crate) fn new(max_requests: usize, window: Duration) -> RateLimiter {
+    RateLimiter {
+      max_requests: max_requests.max(1),
+      window,
+      slots: Mutex::default(),
+    }
+  }
+
+  /// Reserve a slot for a request, returning how long to wait before
+  /// sending it.
+  pub(crate) fn reserve(&self) -> Duration {
+    let mut slots = self.slots.lock().unwrap();
+    let now = Instant::now();
+    while slots.front().is_some_and(|&slot| slot + self.window <= now) {
+      slots.pop_front();
+    }
+    let slot = match slots.len().checked_sub(self.max_requests) {
+      Some(i) => (slots[i] + self.window).max(now),
+      None => now,
+    };
+    slots.push_back(slot);
+    slot - now
+  }
+}
@@ -0,0 +1,81 @@
+//! Everything about a word at once, from
+//! [get_word_report](crate::Wordnik::get_word_report).
+
+use crate::pretty::{self, PrettyOptions};
+use crate::{
+  Audio, Definition, Etymology, Example, Frequency, Operation, Pronunciation,
+  RelatedWords, ScrabbleScore, Syllable, WordnikError,
+};
+
+/// The sections of a word report, one per operation.
+///
+/// A section is `None` if it wasn't asked for or its request failed; the
+/// failures are in [errors](Self::errors), so one unavailable endpoint
+/// doesn't lose the others.
+#[derive(Default, Debug)]
+pub struct WordReport {
+  pub word: String,
+  pub definitions: Option<Vec<Definition>>,
+  pub etymologies: Option<Vec<Etymology>>,
+  pub pronunciations: Option<Vec<Pronunciation>>,
+  pub hyphenation: Option<Vec<Syllable>>,
+  pub top_example: Option<Example>,
+  pub related_words: Option<Vec<RelatedWords>>,
+  pub scrabble_score: Option<ScrabbleScore>,
+  pub frequency: Option<Frequency>,
+  pub audio: Option<Vec<Audio>>,
+  /// The sections that failed, in the order of the fields.
+  pub errors: Vec<(Operation, WordnikError)>,
+}
+
+/// The operations a report is made of, in the order of its sections.
+pub(crate) const REPORT_OPERATIONS: [Operation; 9] = [
+  Operation::Definitions,
+  Operation::Etymologies,
+  Operation::Pronunciations,
+  Operation::Hyphenation,
+  Operation::TopExample,
+  Operation::RelatedWords,
+  Operation::ScrabbleScore,
+  Operation::Frequency,
+  Operation::Audio,
+];
+
+impl WordReport {
+  pub(crate) fn new(word: &str) -> WordReport {
+    WordReport {
+      word: word.to_string(),
+      ..WordReport::default()
+    }
+  }
+
+  /// Keep the outcome of the request for a section, if one was sent,
+  /// returning the section.
+  pub(crate) fn section<T>(
+    &mut self,
+    operation: Operation,
+    res: Option<Result<T, WordnikError>>,
+  ) -> Option<T> {
+    match res? {
+      Ok(section) => Some(section),
+      Err(e) => {
+        self.errors.push((operation, e));
+        None
+      }
+    }
+  }
+
+  /// Format every section that was fetched as text, like
+  /// [get_full_report_pretty](crate::Wordnik::get_full_report_pretty),
+  /// followed by the failed ones and their errors.
+  pub fn pretty(&self) -> String {
+    self.pretty_with(&PrettyOptions::default())
+  }
+
+  /// Format the report like [pretty](Self::pretty), with the definitions
+  /// chosen and grouped, and the text wrapped and colored, as `opts`
+  /// says. Its section switches are ignored.
+  pub fn pretty_with(&self, opts: &PrettyOptions) -> String {
+    pretty::format_word_report(self, opts)
+  }
+}
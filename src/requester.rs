@@ -0,0 +1,338 @@
+use reqwest::header::{HeaderMap, ETAG};
+#[cfg(feature = "blocking")]
+use reqwest::StatusCode;
+use serde_json::Value;
+
+#[cfg(feature = "blocking")]
+use crate::builder::DEFAULT_MAX_RESPONSE_SIZE;
+#[cfg(feature = "blocking")]
+use crate::ratelimit::retry_after;
+#[cfg(feature = "blocking")]
+use crate::trace::RequestTrace;
+use crate::{RateLimitStatus, WordnikError};
+
+/// The HTTP layer of [Wordnik](crate::Wordnik).
+///
+/// [reqwest::blocking::Client] implements it and is used by default.
+/// Implement it yourself to serve canned responses, e.g. in tests. With
+/// the `tracing` feature, calls are still wrapped in a span, but the
+/// events about the request itself are up to your implementation.
+///
+/// Return [WordnikError::NotFound] for a 404, so that e.g.
+/// [word_exists](crate::Wordnik::word_exists) can tell unknown words from
+/// failed requests, and [WordnikError::TooManyRequests] for a 429, so that
+/// [retries](crate::WordnikBuilder::retry) wait as long as the API asks.
+///
+/// # Example
+/// ```
+/// use reqwest::header::HeaderMap;
+/// use serde_json::{json, Value};
+/// use wordnik::{Requester, Wordnik, WordnikError};
+///
+/// struct Canned;
+///
+/// impl Requester for Canned {
+///   fn request(
+///     &self,
+///     _url: &str,
+///     _headers: &HeaderMap,
+///   ) -> Result<Value, WordnikError> {
+///     Ok(json!([{
+///       "word": "word",
+///       "text": "A unit of language.",
+///       "partOfSpeech": "noun",
+///       "attributionText": "from The American Heritage Dictionary",
+///       "sourceDictionary": "ahd-5",
+///       "attributionUrl": "https://ahdictionary.com/",
+///       "wordnikUrl": "https://www.wordnik.com/words/word"
+///     }]))
+///   }
+/// }
+///
+/// let api = Wordnik::with_requester(
+///   "YOUR_API_KEY".to_string(),
+///   "https://api.wordnik.com/v4/word.json/".to_string(),
+///   Canned,
+/// );
+/// let v = api.get_definitions("word").unwrap();
+/// assert_eq!(v[0].definition.as_deref(), Some("A unit of language."));
+/// ```
+///
+/// The API key is passed in the `api_key` header, and never in the URL:
+///
+/// ```
+/// # use reqwest::header::HeaderMap;
+/// # use serde_json::{json, Value};
+/// # use wordnik::{Requester, Wordnik, WordnikError};
+/// struct Check;
+///
+/// impl Requester for Check {
+///   fn request(
+///     &self,
+///     url: &str,
+///     headers: &HeaderMap,
+///   ) -> Result<Value, WordnikError> {
+///     assert_eq!(headers["api_key"], "SECRET");
+///     assert!(!url.contains("SECRET"));
+///     Ok(json!([]))
+///   }
+/// }
+///
+/// let api = Wordnik::with_requester(
+///   "SECRET".to_string(),
+///   "https://api.wordnik.com/v4/word.json/".to_string(),
+///   Check,
+/// );
+/// api.get_definitions("word").unwrap();
+/// ```
+///
+/// Closures with the signature of [request](Requester::request) implement
+/// it too, which is handy for one-off fakes:
+///
+/// ```
+/// use reqwest::header::HeaderMap;
+/// use serde_json::json;
+/// use wordnik::Wordnik;
+///
+/// let api = Wordnik::with_requester(
+///   "YOUR_API_KEY".to_string(),
+///   "https://api.wordnik.com/v4/word.json/".to_string(),
+///   |url: &str, _: &HeaderMap| {
+///     assert!(url.ends_with("/word/hyphenation"));
+///     Ok(json!([{ "text": "word", "seq": 0 }]))
+///   },
+/// );
+/// assert_eq!(api.get_hyphenation("word").unwrap()[0].text, "word");
+/// ```
+pub trait Requester: Send + Sync {
+  /// Send a GET request to `url` with `headers`, which carry the API key,
+  /// and parse the response body as JSON.
+  fn request(
+    &self,
+    url: &str,
+    headers: &HeaderMap,
+  ) -> Result<Value, WordnikError>;
+
+  /// Like [request](Self::request), but also report the `ETag` of the
+  /// response and a `304 Not Modified`, which the cache uses to
+  /// revalidate expired responses with `If-None-Match`.
+  ///
+  /// By default this calls [request](Self::request) and reports no
+  /// `ETag`, so cached responses are simply fetched again.
+  fn request_conditional(
+    &self,
+    url: &str,
+    headers: &HeaderMap,
+  ) -> Result<Fetched, WordnikError> {
+    Ok(Fetched::Body {
+      body: self.request(url, headers)?,
+      etag: None,
+      rate_limit: None,
+    })
+  }
+}
+
+/// A response, as returned by [Requester::request_conditional].
+///
+/// # Example
+///
+/// ```
+/// use reqwest::header::{HeaderMap, IF_NONE_MATCH};
+/// use serde_json::{json, Value};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::time::Duration;
+/// use wordnik::{Fetched, Requester, Wordnik, WordnikError};
+///
+/// struct Server(AtomicBool);
+///
+/// impl Requester for Server {
+///   fn request(&self, _: &str, _: &HeaderMap) -> Result<Value, WordnikError> {
+///     unreachable!()
+///   }
+///
+///   fn request_conditional(
+///     &self,
+///     _url: &str,
+///     headers: &HeaderMap,
+///   ) -> Result<Fetched, WordnikError> {
+///     if self.0.swap(true, Ordering::SeqCst) {
+///       assert_eq!(headers[IF_NONE_MATCH], "\"v1\"");
+///       return Ok(Fetched::NotModified { rate_limit: None });
+///     }
+///     Ok(Fetched::Body {
+///       body: json!([{ "text": "word" }]),
+///       etag: Some("\"v1\"".to_string()),
+///       rate_limit: None,
+///     })
+///   }
+/// }
+///
+/// // Every response expires at once, but is revalidated with its ETag.
+/// let api = Wordnik::builder()
+///   .api_key("YOUR_API_KEY")
+///   .cache(Duration::ZERO, 100)
+///   .requester(Server(AtomicBool::new(false)))
+///   .build()
+///   .unwrap();
+/// assert_eq!(api.get_hyphenation("word").unwrap()[0].text, "word");
+/// assert_eq!(api.get_hyphenation("word").unwrap()[0].text, "word");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Fetched {
+  /// The parsed body, and the `ETag` header if there was one.
+  Body {
+    body: Value,
+    etag: Option<String>,
+    rate_limit: Option<RateLimitStatus>,
+  },
+  /// `304 Not Modified`: the cached response is still current.
+  NotModified { rate_limit: Option<RateLimitStatus> },
+}
+
+#[cfg(feature = "blocking")]
+impl Requester for reqwest::blocking::Client {
+  fn request(
+    &self,
+    url: &str,
+    headers: &HeaderMap,
+  ) -> Result<Value, WordnikError> {
+    match self.request_conditional(url, headers)? {
+      Fetched::Body { body, .. } => Ok(body),
+      Fetched::NotModified { .. } => Err(WordnikError::Json(
+        serde::de::Error::custom("unexpected 304 Not Modified"),
+      )),
+    }
+  }
+
+  fn request_conditional(
+    &self,
+    url: &str,
+    headers: &HeaderMap,
+  ) -> Result<Fetched, WordnikError> {
+    fetch(self, url, headers, DEFAULT_MAX_RESPONSE_SIZE)
+  }
+}
+
+/// The blocking client built by [WordnikBuilder](crate::WordnikBuilder),
+/// with its [maximum response size](crate::WordnikBuilder::max_response_size).
+#[cfg(feature = "blocking")]
+pub(crate) struct SizeLimited {
+  pub(crate) client: reqwest::blocking::Client,
+  pub(crate) max_response_size: u64,
+}
+
+#[cfg(feature = "blocking")]
+impl Requester for SizeLimited {
+  fn request(
+    &self,
+    url: &str,
+    headers: &HeaderMap,
+  ) -> Result<Value, WordnikError> {
+    match self.request_conditional(url, headers)? {
+      Fetched::Body { body, .. } => Ok(body),
+      Fetched::NotModified { .. } => Err(WordnikError::Json(
+        serde::de::Error::custom("unexpected 304 Not Modified"),
+      )),
+    }
+  }
+
+  fn request_conditional(
+    &self,
+    url: &str,
+    headers: &HeaderMap,
+  ) -> Result<Fetched, WordnikError> {
+    fetch(&self.client, url, headers, self.max_response_size)
+  }
+}
+
+/// Send a request with `client`, reading at most `max_response_size`
+/// bytes of the body.
+#[cfg(feature = "blocking")]
+fn fetch(
+  client: &reqwest::blocking::Client,
+  url: &str,
+  headers: &HeaderMap,
+  max_response_size: u64,
+) -> Result<Fetched, WordnikError> {
+  use std::io::Read;
+
+  let trace = RequestTrace::start(url);
+  let res = client.get(url).headers(headers.clone()).send()?;
+  trace.status(res.status());
+  let rate_limit = RateLimitStatus::from_headers(res.headers());
+  match res.status() {
+    StatusCode::NOT_FOUND => return Err(WordnikError::NotFound),
+    StatusCode::NOT_MODIFIED => return Ok(Fetched::NotModified { rate_limit }),
+    StatusCode::TOO_MANY_REQUESTS => {
+      return Err(WordnikError::TooManyRequests(retry_after(res.headers())))
+    }
+    _ => {}
+  }
+  let res = res.error_for_status()?;
+  let etag = etag(res.headers());
+  check_size(res.content_length(), max_response_size)?;
+  let mut res_body = Vec::new();
+  res
+    .take(max_response_size.saturating_add(1))
+    .read_to_end(&mut res_body)
+    .map_err(read_error)?;
+  check_size(Some(res_body.len() as u64), max_response_size)?;
+  trace.body(res_body.len());
+
+  let body: Value = serde_json::from_slice(&res_body)?;
+  Ok(Fetched::Body {
+    body,
+    etag,
+    rate_limit,
+  })
+}
+
+/// Turn an error reading a body back into the [reqwest::Error] it wraps,
+/// e.g. a timeout, which [Retry](crate::retry::Retry) may retry, like the
+/// async client's.
+#[cfg(feature = "blocking")]
+fn read_error(e: std::io::Error) -> WordnikError {
+  let kind = e.kind();
+  let inner = e
+    .into_inner()
+    .map(|inner| inner.downcast::<reqwest::Error>());
+  match inner {
+    Some(Ok(e)) => WordnikError::from(*e),
+    Some(Err(inner)) => {
+      serde_json::Error::io(std::io::Error::new(kind, inner)).into()
+    }
+    None => serde_json::Error::io(kind.into()).into(),
+  }
+}
+
+/// Fail if a body of `len` bytes is longer than `max_response_size`.
+pub(crate) fn check_size(
+  len: Option<u64>,
+  max_response_size: u64,
+) -> Result<(), WordnikError> {
+  match len {
+    Some(len) if len > max_response_size => {
+      Err(WordnikError::ResponseTooLarge(max_response_size))
+    }
+    _ => Ok(()),
+  }
+}
+
+pub(crate) fn etag(headers: &HeaderMap) -> Option<String> {
+  let etag = headers.get(ETAG)?.to_str().ok()?;
+  Some(etag.to_string())
+}
+
+impl<F> Requester for F
+where
+  F: Fn(&str, &HeaderMap) -> Result<Value, WordnikError> + Send + Sync,
+{
+  fn request(
+    &self,
+    url: &str,
+    headers: &HeaderMap,
+  ) -> Result<Value, WordnikError> {
+    self(url, headers)
+  }
+}
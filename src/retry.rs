@@ -0,0 +1,50 @@
+//! Retrying requests that failed for reasons likely to pass, turned on
+//! with [WordnikBuilder::retry](crate::WordnikBuilder::retry).
+
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use crate::{Fetched, WordnikError};
+
+/// How often, and after how long, failed requests are retried. By default
+/// they aren't.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Retry {
+  pub(crate) max_retries: usize,
+  /// The wait before the first retry, doubled for each one after it.
+  pub(crate) backoff: Duration,
+}
+
+impl Retry {
+  /// How long to wait before retrying the request that got `res` after
+  /// `retries` retries, or `None` to give up, or because it succeeded.
+  /// A 429 waits at least as long as its `Retry-After` asked.
+  pub(crate) fn delay(
+    &self,
+    retries: usize,
+    res: &Result<Fetched, WordnikError>,
+  ) -> Option<Duration> {
+    let mut retry_after = None;
+    let transient = match res {
+      Err(WordnikError::TooManyRequests(wait)) => {
+        retry_after = *wait;
+        true
+      }
+      Err(WordnikError::Http(e)) => {
+        e.is_timeout()
+          || e.is_connect()
+          || e.status().is_some_and(|status| {
+            status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+          })
+      }
+      _ => false,
+    };
+    if !transient || retries >= self.max_retries {
+      return None;
+    }
+    let factor = 1u32.checked_shl(retries as u32).unwrap_or(u32::MAX);
+    let backoff = self.backoff.saturating_mul(factor);
+    Some(backoff.max(retry_after.unwrap_or_default()))
+  }
+}
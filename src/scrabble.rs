@@ -0,0 +1,39 @@
+//! Scrabble letter scores, computed locally since the API only returns a
+//! word's total.
+
+/// The points of each letter of `word` on a standard English Scrabble
+/// tile, in order. Letters are case-insensitive; characters that aren't
+/// letters, such as spaces and hyphens, are skipped, and letters without
+/// a tile, such as `é`, score 0.
+///
+/// # Example
+///
+/// ```
+/// use wordnik::scrabble_breakdown;
+///
+/// let breakdown = scrabble_breakdown("Quiz");
+/// assert_eq!(breakdown, [('q', 10), ('u', 1), ('i', 1), ('z', 10)]);
+/// let total: u32 = breakdown.iter().map(|&(_, points)| points as u32).sum();
+/// assert_eq!(total, 22);
+/// ```
+pub fn scrabble_breakdown(word: &str) -> Vec<(char, u8)> {
+  word
+    .chars()
+    .filter(|c| c.is_alphabetic())
+    .flat_map(char::to_lowercase)
+    .map(|c| (c, tile_value(c)))
+    .collect()
+}
+
+fn tile_value(letter: char) -> u8 {
+  match letter {
+    'a' | 'e' | 'i' | 'l' | 'n' | 'o' | 'r' | 's' | 't' | 'u' => 1,
+    'd' | 'g' => 2,
+    'b' | 'c' | 'm' | 'p' => 3,
+    'f' | 'h' | 'v' | 'w' | 'y' => 4,
+    'k' => 5,
+    'j' | 'x' => 8,
+    'q' | 'z' => 10,
+    _ => 0,
+  }
+}
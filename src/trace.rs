@@ -0,0 +1,44 @@
+//! Events about requests, emitted with the `tracing` feature.
+//!
+//! Without the feature everything here compiles to nothing. The API key
+//! travels in a header, which is never recorded.
+
+use reqwest::StatusCode;
+
+/// Follows one request from being sent to its body being read.
+pub(crate) struct RequestTrace {
+  #[cfg(feature = "tracing")]
+  start: std::time::Instant,
+}
+
+impl RequestTrace {
+  #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+  pub(crate) fn start(url: &str) -> RequestTrace {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(url, "sending request");
+    RequestTrace {
+      #[cfg(feature = "tracing")]
+      start: std::time::Instant::now(),
+    }
+  }
+
+  #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+  pub(crate) fn status(&self, status: StatusCode) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+      status = status.as_u16(),
+      elapsed_ms = self.start.elapsed().as_millis() as u64,
+      "received response"
+    );
+  }
+
+  #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+  pub(crate) fn body(&self, bytes: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+      bytes,
+      elapsed_ms = self.start.elapsed().as_millis() as u64,
+      "read response body"
+    );
+  }
+}